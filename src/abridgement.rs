@@ -0,0 +1,91 @@
+use std::ops::Range;
+
+use super::{AlignedSpan, Coordinates, Output, X, Y};
+
+/// Options for aligning texts where one side omits large spans of the other, such as abridged or
+/// censored editions
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Abridgement {
+    /// Expected fraction of the sentences of the longer text without a counterpart, by which the
+    /// `min_coverage` is reduced. Defaults to `0.2`
+    pub deletion_rate: f32,
+    /// Minimum number of consecutive sentences without a counterpart considered a skip. Corridors between
+    /// anchors are widened to admit skips at any position, and skips are reported by `Output::skips`.
+    /// Defaults to `10`
+    pub min_skip: usize,
+}
+
+impl Default for Abridgement {
+    fn default() -> Self {
+        Self {
+            deletion_rate: 0.2,
+            min_skip: 10,
+        }
+    }
+}
+
+/// A span between consecutive anchors where one text has at least `Abridgement::min_skip` more sentences
+/// than the other, whose excess sentences are presumed omitted from the other text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Skip {
+    /// Range of the sentences of text `a` between the anchors
+    pub a: Range<usize>,
+    /// Range of the sentences of text `b` between the anchors
+    pub b: Range<usize>,
+}
+
+impl Skip {
+    /// Returns the number of sentences presumed omitted
+    pub fn len(&self) -> usize {
+        self.a.len().abs_diff(self.b.len())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the span of the sentences of both texts between the anchors
+    pub fn span(&self) -> AlignedSpan {
+        AlignedSpan {
+            a_range: self.a.clone(),
+            b_range: self.b.clone(),
+            confidence: 0.0,
+        }
+    }
+}
+
+impl<'a, T, U> Output<'a, T, U> {
+    /// Returns the skips between consecutive anchors, in text order. Always empty unless the texts
+    /// were aligned with an `Abridgement`
+    pub fn skips(&self) -> Vec<Skip> {
+        let Some(abridgement) = self.abridgement else {
+            return vec![];
+        };
+
+        let mut anchors = self
+            .sat
+            .anchors()
+            .map(|Coordinates(X(x), Y(y))| (y + 1, x + 1))
+            .collect::<Vec<_>>();
+        anchors.sort_unstable();
+        anchors.insert(0, (0, 0));
+        anchors.push((self.a.len() + 1, self.b.len() + 1));
+
+        anchors
+            .windows(2)
+            .filter_map(|window| {
+                let (start, end) = (window[0], window[1]);
+                if end.0 <= start.0 || end.1 <= start.1 {
+                    return None;
+                }
+
+                // Anchors are offset by one so that the virtual anchors bound the texts
+                let skip = Skip {
+                    a: start.0..end.0 - 1,
+                    b: start.1..end.1 - 1,
+                };
+                (skip.len() >= abridgement.min_skip).then_some(skip)
+            })
+            .collect()
+    }
+}
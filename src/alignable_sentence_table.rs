@@ -1,74 +1,227 @@
-use std::collections::{HashMap, HashSet};
+use std::mem::size_of;
+use std::ops::RangeInclusive;
 
 use super::sentence_alignment_table::SentenceAlignmentTable;
-use super::{Coordinates, X, Y};
+use super::{Abridgement, Coordinates, X, Y};
 
-#[derive(Default, Debug)]
-pub struct AlignableSentenceTable(HashMap<X, HashSet<Y>>);
+/// Corridors of alignable cells between consecutive anchors. Cells are not materialized, but derived
+/// from the chain of anchors and the width of the corridor at each position
+#[derive(Debug)]
+pub struct AlignableSentenceTable {
+    anchors: Vec<Coordinates>,
+    width: f32,
+    min_skip: Option<usize>,
+    len: usize,
+}
 
 impl AlignableSentenceTable {
-    pub(crate) fn contains(&self, Coordinates(x, y): Coordinates) -> bool {
-        self.0.get(&x).map(|set| set.contains(&y)).unwrap_or(false)
+    pub(crate) fn contains(&self, coordinates @ Coordinates(x, y): Coordinates) -> bool {
+        let i = self.anchors.partition_point(|anchor| anchor.x() < x);
+        (i.saturating_sub(1)..=i)
+            .filter(|&i| i + 1 < self.anchors.len())
+            .any(|i| {
+                let (start, end) = (self.anchors[i], self.anchors[i + 1]);
+                start.x() <= x
+                    && x <= end.x()
+                    && start.y() <= y
+                    && y <= end.y()
+                    && self.segment_contains(start, end, coordinates)
+            })
     }
 
-    pub(crate) fn insert(&mut self, Coordinates(x, y): Coordinates) {
-        self.0.entry(x).or_default().insert(y);
+    /// Returns the approximate memory held by the table, in bytes
+    pub(crate) fn memory(&self) -> usize {
+        self.anchors.capacity() * size_of::<Coordinates>()
     }
 
-    pub(crate) fn all(&self) -> impl Iterator<Item = Coordinates> + '_ {
-        self.0
-            .iter()
-            .flat_map(|(x, ys)| ys.iter().map(|y| Coordinates(*x, *y)))
+    /// Returns the number of alignable cells
+    pub(crate) fn len(&self) -> usize {
+        self.len
     }
-}
-
-impl From<&SentenceAlignmentTable> for AlignableSentenceTable {
-    fn from(sat: &SentenceAlignmentTable) -> Self {
-        let mut ast = Self::default();
 
-        let mut start = sat.next_anchor(None);
-        let mut end = sat.next_anchor(Some(start));
+    pub(crate) fn all(&self) -> impl Iterator<Item = Coordinates> + '_ {
+        self.anchors
+            .windows(2)
+            .enumerate()
+            .flat_map(move |(i, segment)| {
+                // Consecutive corridors share only the anchor between them
+                self.segment(segment[0], segment[1])
+                    .filter(move |&coordinates| i == 0 || coordinates != segment[0])
+            })
+    }
 
-        while start != end {
-            let x_distance = (end.x().0 - start.x().0) as f32;
-            let y_distance = (end.y().0 - start.y().0) as f32;
+    /// Returns the alignable cells of each row, i.e. the ranges of the sentences of text `b` alignable with
+    /// each sentence of text `a`, without enumerating the cells one at a time where the corridor is wider
+    /// along text `b`
+    pub(crate) fn rows(&self) -> Vec<Vec<RangeInclusive<usize>>> {
+        let mut rows: Vec<Vec<RangeInclusive<usize>>> =
+            vec![vec![]; self.anchors.last().map_or(0, |anchor| anchor.y().0 + 1)];
+        // Corridors are ordered along both texts, so the ranges of each row only ever extend to the right
+        let mut extend = |y: usize, range: RangeInclusive<usize>| match rows[y].last_mut() {
+            Some(last) if *range.start() <= last.end() + 1 => {
+                *last = *last.start()..=*last.end().max(range.end());
+            }
+            _ => rows[y].push(range),
+        };
 
-            if x_distance > y_distance {
+        for segment in self.anchors.windows(2) {
+            let (start, end) = (segment[0], segment[1]);
+            if end.x().0 - start.x().0 > end.y().0 - start.y().0 {
                 for y in start.y().0..=end.y().0 {
-                    let progress = (y - start.y().0) as f32 / y_distance;
-                    let scale = (0.5 - progress).abs() / 0.5;
-                    let n = (x_distance.sqrt() - scale * x_distance.sqrt())
-                        .min(x_distance.sqrt())
-                        .max(1.0) as usize;
-
-                    let diagonal = start.x().0 as f32 + (progress * x_distance);
-                    let min = (diagonal - n as f32 / 2.0).floor().max(start.x().0 as f32) as usize;
-                    let max = (diagonal + n as f32 / 2.0).floor().min(end.x().0 as f32) as usize;
-                    for x in min..=max {
-                        ast.insert(Coordinates(X(x), Y(y)))
-                    }
+                    extend(y, self.band(start, end, y));
                 }
             } else {
                 for x in start.x().0..=end.x().0 {
-                    let progress = (x - start.x().0) as f32 / x_distance;
-                    let scale = (0.5 - progress).abs() / 0.5;
-                    let n = (y_distance.sqrt() - scale * y_distance.sqrt())
-                        .min(y_distance.sqrt())
-                        .max(1.0) as usize;
-
-                    let diagonal = start.y().0 as f32 + (progress * y_distance);
-                    let min = (diagonal - n as f32 / 2.0).floor().max(start.y().0 as f32) as usize;
-                    let max = (diagonal + n as f32 / 2.0).floor().min(end.y().0 as f32) as usize;
-                    for y in min..=max {
-                        ast.insert(Coordinates(X(x), Y(y)))
+                    for y in self.band(start, end, x) {
+                        extend(y, x..=x);
                     }
                 }
             }
-
-            start = end;
-            end = sat.next_anchor(Some(start));
         }
 
+        rows
+    }
+
+    /// Builds the corridors between the anchors of `sat`, with their widths scaled by `width`. When the
+    /// texts are abridged, corridors between anchors whose distances differ by a skip are widened to
+    /// admit the skip at any position between the anchors. `hints` are treated as further anchors,
+    /// except where they cross or coincide with an actual anchor
+    pub(crate) fn new(
+        sat: &SentenceAlignmentTable,
+        width: f32,
+        abridgement: Option<&Abridgement>,
+        hints: &[Coordinates],
+    ) -> Self {
+        let mut anchors = sat.chain();
+        let hints = hints
+            .iter()
+            .copied()
+            .filter(|hint| {
+                anchors.iter().all(|anchor| {
+                    (hint.x() < anchor.x() && hint.y() < anchor.y())
+                        || (hint.x() > anchor.x() && hint.y() > anchor.y())
+                })
+            })
+            .collect::<Vec<_>>();
+        anchors.extend(hints);
+        anchors.sort_unstable_by_key(|anchor| (anchor.x(), anchor.y()));
+
+        Self::from_anchors(
+            anchors,
+            width,
+            abridgement.map(|abridgement| abridgement.min_skip),
+        )
+    }
+
+    /// Builds the corridors from `start` to `end` through the anchors of `sat` strictly between them, with
+    /// their widths scaled by `width`
+    pub(crate) fn within(
+        sat: &SentenceAlignmentTable,
+        width: f32,
+        start: Coordinates,
+        end: Coordinates,
+    ) -> Self {
+        let mut anchors = vec![start];
+        anchors.extend(sat.chain().into_iter().filter(|anchor| {
+            start.x() < anchor.x()
+                && start.y() < anchor.y()
+                && anchor.x() < end.x()
+                && anchor.y() < end.y()
+        }));
+        anchors.push(end);
+
+        Self::from_anchors(anchors, width, None)
+    }
+
+    fn from_anchors(anchors: Vec<Coordinates>, width: f32, min_skip: Option<usize>) -> Self {
+        let mut ast = Self {
+            anchors,
+            width,
+            min_skip,
+            len: 0,
+        };
+        ast.len = ast
+            .anchors
+            .windows(2)
+            .map(|segment| {
+                let (start, end) = (segment[0], segment[1]);
+                if end.x().0 - start.x().0 > end.y().0 - start.y().0 {
+                    (start.y().0..=end.y().0)
+                        .map(|y| ast.band(start, end, y).count())
+                        .sum::<usize>()
+                } else {
+                    (start.x().0..=end.x().0)
+                        .map(|x| ast.band(start, end, x).count())
+                        .sum()
+                }
+            })
+            .sum::<usize>()
+            .saturating_sub(ast.anchors.len().saturating_sub(2));
+
         ast
     }
+
+    fn segment_contains(
+        &self,
+        start: Coordinates,
+        end: Coordinates,
+        Coordinates(x, y): Coordinates,
+    ) -> bool {
+        if end.x().0 - start.x().0 > end.y().0 - start.y().0 {
+            self.band(start, end, y.0).contains(&x.0)
+        } else {
+            self.band(start, end, x.0).contains(&y.0)
+        }
+    }
+
+    fn segment(
+        &self,
+        start: Coordinates,
+        end: Coordinates,
+    ) -> Box<dyn Iterator<Item = Coordinates> + '_> {
+        if end.x().0 - start.x().0 > end.y().0 - start.y().0 {
+            Box::new((start.y().0..=end.y().0).flat_map(move |y| {
+                self.band(start, end, y)
+                    .map(move |x| Coordinates(X(x), Y(y)))
+            }))
+        } else {
+            Box::new((start.x().0..=end.x().0).flat_map(move |x| {
+                self.band(start, end, x)
+                    .map(move |y| Coordinates(X(x), Y(y)))
+            }))
+        }
+    }
+
+    /// Returns the range of the corridor between `start` and `end` along the axis on which they are further
+    /// apart, at position `i` of the other axis
+    fn band(&self, start: Coordinates, end: Coordinates, i: usize) -> RangeInclusive<usize> {
+        let x_distance = (end.x().0 - start.x().0) as f32;
+        let y_distance = (end.y().0 - start.y().0) as f32;
+        let ((short_start, short_end), (long_start, long_end)) = if x_distance > y_distance {
+            ((start.y().0, end.y().0), (start.x().0, end.x().0))
+        } else {
+            ((start.x().0, end.x().0), (start.y().0, end.y().0))
+        };
+        let short_distance = (short_end - short_start) as f32;
+        let long_distance = (long_end - long_start) as f32;
+
+        let progress = (i - short_start) as f32 / short_distance;
+        let scale = (0.5 - progress).abs() / 0.5;
+        let n = ((long_distance.sqrt() - scale * long_distance.sqrt()).min(long_distance.sqrt())
+            * self.width)
+            .max(1.0) as usize;
+
+        let diagonal = long_start as f32 + (progress * long_distance);
+        let mut min = (diagonal - n as f32 / 2.0).floor().max(long_start as f32) as usize;
+        let mut max = (diagonal + n as f32 / 2.0).floor().min(long_end as f32) as usize;
+        if self
+            .min_skip
+            .is_some_and(|min_skip| long_distance - short_distance >= min_skip as f32)
+        {
+            min = min.min(long_start + (i - short_start));
+            max = max.max(long_end - (short_end - i));
+        }
+
+        min..=max
+    }
 }
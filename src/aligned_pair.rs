@@ -0,0 +1,28 @@
+use super::sentence_alignment_table::Score;
+use super::{Coordinates, X, Y};
+
+/// A pair of sentence indices of texts `a` and `b`, with the score of the pair in the SAT
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AlignedPair {
+    /// Index of the sentence in text `a`
+    pub a: usize,
+    /// Index of the sentence in text `b`
+    pub b: usize,
+    /// Score of the pair in the SAT, in the units of the `Config::anchor_threshold`
+    pub score: usize,
+}
+
+impl AlignedPair {
+    pub(crate) fn new(Coordinates(X(x), Y(y)): Coordinates, Score(score): Score) -> Self {
+        Self { a: y, b: x, score }
+    }
+
+    /// Returns the sentence indices `(a, b)`
+    pub fn indices(&self) -> (usize, usize) {
+        (self.a, self.b)
+    }
+
+    pub(crate) fn coordinates(&self) -> Coordinates {
+        Coordinates(X(self.b), Y(self.a))
+    }
+}
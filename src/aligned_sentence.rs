@@ -0,0 +1,135 @@
+use super::{AlignedSpan, Coordinates, Output, Partner, Side, Status, X, Y};
+
+/// A sentence together with the outcome of its alignment, see `Output::a` and `Output::b`
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlignedSentence<'a, S> {
+    /// Index of the sentence in its text
+    pub index: usize,
+    pub sentence: &'a S,
+    pub status: Status,
+    /// Block of sentences connected to this sentence by alignments, or `None` if it is not aligned
+    pub block: Option<AlignedSpan>,
+}
+
+impl<'a, S> AlignedSentence<'a, S> {
+    /// Returns the partners of the sentence, ordered by index
+    pub fn partners(&self) -> &[Partner] {
+        match &self.status {
+            Status::Aligned(partners) => partners,
+            _ => &[],
+        }
+    }
+}
+
+impl<'a, T, U> Output<'a, T, U> {
+    /// Returns the sentence at index `i` of text `a` with its partners, status and block
+    pub fn a(&self, i: usize) -> AlignedSentence<'_, T> {
+        AlignedSentence {
+            index: i,
+            sentence: &self.a[i],
+            status: self.a_status(i),
+            block: self.block(Side::A, i),
+        }
+    }
+
+    /// Returns the sentence at index `i` of text `b` with its partners, status and block
+    pub fn b(&self, i: usize) -> AlignedSentence<'_, U> {
+        AlignedSentence {
+            index: i,
+            sentence: &self.b[i],
+            status: self.b_status(i),
+            block: self.block(Side::B, i),
+        }
+    }
+
+    /// Returns the blocks of sentences of both texts connected to one another by alignments, e.g. the two
+    /// sentences of text `b` into which a sentence of text `a` was split, in text order
+    pub fn blocks(&self) -> Vec<AlignedSpan> {
+        let mut aligned = self.a_alignments.keys().map(|y| y.0).collect::<Vec<_>>();
+        aligned.sort_unstable();
+
+        let mut blocks: Vec<AlignedSpan> = vec![];
+        for i in aligned {
+            if blocks
+                .last()
+                .is_none_or(|block| !block.a_range.contains(&i))
+            {
+                blocks.extend(self.block(Side::A, i));
+            }
+        }
+
+        blocks
+    }
+
+    fn block(&self, side: Side, i: usize) -> Option<AlignedSpan> {
+        let (mut a_stack, mut b_stack) = match side {
+            Side::A => (vec![Y(i)], vec![]),
+            Side::B => (vec![], vec![X(i)]),
+        };
+        let (mut a_block, mut b_block): (Vec<Y>, Vec<X>) = (vec![], vec![]);
+
+        while !a_stack.is_empty() || !b_stack.is_empty() {
+            while let Some(y) = a_stack.pop() {
+                if !a_block.contains(&y) {
+                    a_block.push(y);
+                    b_stack.extend(self.a_alignments.get(&y).into_iter().flatten());
+                }
+            }
+            while let Some(x) = b_stack.pop() {
+                if !b_block.contains(&x) {
+                    b_block.push(x);
+                    a_stack.extend(self.b_alignment_map().get(&x).into_iter().flatten());
+                }
+            }
+        }
+
+        let range = |indices: &mut dyn Iterator<Item = usize>| {
+            let (min, max) =
+                indices.fold((usize::MAX, 0), |(min, max), i| (min.min(i), max.max(i)));
+            min..max + 1
+        };
+        (!a_block.is_empty() && !b_block.is_empty()).then(|| {
+            let threshold = self.sat.anchor_threshold().0.max(1) as f32;
+            let scores = a_block
+                .iter()
+                .flat_map(|&y| {
+                    self.a_alignments
+                        .get(&y)
+                        .into_iter()
+                        .flatten()
+                        .map(move |&x| self.sat.score(Coordinates(x, y)).0)
+                })
+                .collect::<Vec<_>>();
+
+            AlignedSpan {
+                a_range: range(&mut a_block.iter().map(|y| y.0)),
+                b_range: range(&mut b_block.iter().map(|x| x.0)),
+                confidence: scores.iter().sum::<usize>() as f32
+                    / (scores.len().max(1) as f32 * threshold),
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Config, Status};
+
+    #[test]
+    fn unaligned_sentence_has_no_block() {
+        let a = [vec!["one", "two"], vec!["three"], vec!["four", "five"]];
+        let b = [vec!["uno", "dos"], vec!["cuatro", "cinco"]];
+        let a = a.iter().map(Vec::as_slice).collect::<Vec<_>>();
+        let b = b.iter().map(Vec::as_slice).collect::<Vec<_>>();
+        let output = Config {
+            locked_anchors: vec![(0, 0), (2, 1)],
+            ..Default::default()
+        }
+        .align(&a, &b);
+
+        let sentence = output.a(1);
+        assert_eq!(sentence.status, Status::Gap);
+        assert_eq!(sentence.block, None);
+        assert!(output.a(0).block.is_some());
+    }
+}
@@ -0,0 +1,35 @@
+use std::ops::Range;
+
+/// Ranges of sentences of both texts which correspond to one another as a whole, e.g. the block of
+/// sentences connected by alignments, or a gap and the sentences facing it
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlignedSpan {
+    /// Range of the sentences of text `a`
+    pub a_range: Range<usize>,
+    /// Range of the sentences of text `b`
+    pub b_range: Range<usize>,
+    /// Mean score of the aligned pairs within the span relative to the anchor threshold, or `0` if the
+    /// span contains no aligned pair
+    pub confidence: f32,
+}
+
+impl AlignedSpan {
+    /// Returns true if the spans share sentences of either text
+    pub fn overlaps(&self, other: &Self) -> bool {
+        let overlap = |a: &Range<usize>, b: &Range<usize>| a.start < b.end && b.start < a.end;
+        overlap(&self.a_range, &other.a_range) || overlap(&self.b_range, &other.b_range)
+    }
+
+    /// Returns true if the ranges of `other` lie within those of the span in both texts
+    pub fn contains(&self, other: &Self) -> bool {
+        let contains = |a: &Range<usize>, b: &Range<usize>| {
+            b.is_empty() || (a.start <= b.start && b.end <= a.end)
+        };
+        contains(&self.a_range, &other.a_range) && contains(&self.b_range, &other.b_range)
+    }
+
+    /// Returns true if the pair of sentence indices `(a, b)` lies within the span
+    pub fn contains_pair(&self, a: usize, b: usize) -> bool {
+        self.a_range.contains(&a) && self.b_range.contains(&b)
+    }
+}
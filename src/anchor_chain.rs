@@ -0,0 +1,101 @@
+use std::ops::Range;
+
+use super::{Coordinates, Output, X, Y};
+
+/// The monotone chain of anchors which delimits the corridors of the alignment, as pairs of sentence
+/// indices `(a, b)` increasing in both texts
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnchorChain {
+    anchors: Vec<(usize, usize)>,
+    a_len: usize,
+    b_len: usize,
+}
+
+/// The sentences between two consecutive anchors of an `AnchorChain`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Interval {
+    /// Anchor preceding the interval, or `None` at the start of the texts
+    pub start: Option<(usize, usize)>,
+    /// Anchor following the interval, or `None` at the end of the texts
+    pub end: Option<(usize, usize)>,
+    /// Range of the sentences of text `a` strictly between the anchors
+    pub a: Range<usize>,
+    /// Range of the sentences of text `b` strictly between the anchors
+    pub b: Range<usize>,
+}
+
+impl AnchorChain {
+    /// Returns an iterator of the anchors, in text order
+    pub fn iter(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.anchors.iter().copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.anchors.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.anchors.is_empty()
+    }
+
+    /// Binary searches the chain for an anchor at sentence `a` of text `a`, returning its index in the
+    /// chain, or the index at which such an anchor would be inserted
+    pub fn search_a(&self, a: usize) -> Result<usize, usize> {
+        self.anchors.binary_search_by_key(&a, |&(a, _)| a)
+    }
+
+    /// Binary searches the chain for an anchor at sentence `b` of text `b`, returning its index in the
+    /// chain, or the index at which such an anchor would be inserted
+    pub fn search_b(&self, b: usize) -> Result<usize, usize> {
+        self.anchors.binary_search_by_key(&b, |&(_, b)| b)
+    }
+
+    /// Returns the interval between consecutive anchors containing sentence `a` of text `a`, or `None` if
+    /// the sentence is itself an anchor of the chain
+    pub fn a_interval(&self, a: usize) -> Option<Interval> {
+        self.search_a(a).err().map(|i| self.interval(i))
+    }
+
+    /// Returns the interval between consecutive anchors containing sentence `b` of text `b`, or `None` if
+    /// the sentence is itself an anchor of the chain
+    pub fn b_interval(&self, b: usize) -> Option<Interval> {
+        self.search_b(b).err().map(|i| self.interval(i))
+    }
+
+    /// Returns an iterator of all the intervals of the chain, including empty intervals between adjacent
+    /// anchors, in text order
+    pub fn intervals(&self) -> impl Iterator<Item = Interval> + '_ {
+        (0..=self.anchors.len()).map(|i| self.interval(i))
+    }
+
+    /// Returns the interval preceding the anchor at index `i` of the chain
+    fn interval(&self, i: usize) -> Interval {
+        let start = i.checked_sub(1).map(|i| self.anchors[i]);
+        let end = self.anchors.get(i).copied();
+
+        Interval {
+            start,
+            end,
+            a: start.map_or(0, |(a, _)| a + 1)..end.map_or(self.a_len, |(a, _)| a),
+            b: start.map_or(0, |(_, b)| b + 1)..end.map_or(self.b_len, |(_, b)| b),
+        }
+    }
+}
+
+impl<'a, T, U> Output<'a, T, U> {
+    /// Returns the chain of anchors delimiting the corridors of the alignment. Anchors crossing the chain
+    /// are not part of it, though they are still reported by `Output::pairs`
+    pub fn anchor_chain(&self) -> AnchorChain {
+        AnchorChain {
+            anchors: self
+                .sat
+                .chain()
+                .into_iter()
+                .filter(|&coordinates| self.sat.is_anchor(coordinates))
+                .map(|Coordinates(X(x), Y(y))| (y, x))
+                .collect(),
+            a_len: self.a.len(),
+            b_len: self.b.len(),
+        }
+    }
+}
@@ -0,0 +1,305 @@
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::num::NonZeroUsize;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+use super::hash::HashMap;
+use super::{Config, Sentence, Spanned};
+
+type BatchConfigFactory = Box<dyn Fn() -> Config<String> + Sync>;
+/// Splits a sentence into its words
+pub type Segmentation = Box<dyn Fn(&str) -> Vec<String> + Sync>;
+
+/// Alignment of a collection of documents, pairing the files of a directory tree by name and aligning
+/// the pairs on as many threads as are available. Each file holds one sentence per line, blank lines
+/// being skipped. The pairs are written to a file of the output directory at the same relative path as
+/// the pair, named after it with the extension `tsv`, see `Output::export_spans` (the spans being those
+/// of the lines of the files), and the report of every document to `report.tsv`
+pub struct Batch {
+    /// Pattern of the names of the files of text `a`, in which a single `*` stands for the name shared
+    /// with the file of text `b` in the same directory. Defaults to `*.a.txt`
+    pub a_pattern: String,
+    /// Pattern of the names of the files of text `b`. Defaults to `*.b.txt`
+    pub b_pattern: String,
+    /// Provides the configuration with which each pair is aligned. Defaults to `Config::default`
+    pub config: BatchConfigFactory,
+    /// Splits each line into words. Defaults to splitting on whitespace
+    pub segmentation: Segmentation,
+    /// Maximum number of pairs aligned concurrently. Defaults to the available parallelism
+    pub threads: NonZeroUsize,
+}
+
+impl Default for Batch {
+    fn default() -> Self {
+        Self {
+            a_pattern: "*.a.txt".to_owned(),
+            b_pattern: "*.b.txt".to_owned(),
+            config: Box::new(Config::default),
+            segmentation: Box::new(|line| line.split_whitespace().map(String::from).collect()),
+            threads: thread::available_parallelism().unwrap_or(NonZeroUsize::MIN),
+        }
+    }
+}
+
+/// Result of the alignment of one document of a batch
+#[derive(Debug, Clone, PartialEq)]
+pub struct DocumentReport {
+    /// Path of the pair relative to the input directory, its shared name in place of the `*`, or of the
+    /// file if it has no counterpart
+    pub name: PathBuf,
+    pub a_sentences: usize,
+    pub b_sentences: usize,
+    /// Number of aligned pairs
+    pub pairs: usize,
+    /// Final coverage of the alignment
+    pub coverage: f32,
+    /// Reason for which the document was not aligned, or its pairs not written
+    pub error: Option<String>,
+}
+
+impl Batch {
+    /// Aligns the pairs of files found in the tree of `input`, writing their pairs and the report to
+    /// `output`, which is created if it does not exist. Returns the report of every document, ordered by
+    /// name. A document which fails is reported without interrupting the others, and files matching one
+    /// pattern without a counterpart are reported as unpaired
+    pub fn run(
+        &self,
+        input: impl AsRef<Path>,
+        output: impl AsRef<Path>,
+    ) -> io::Result<Vec<DocumentReport>> {
+        let (input, output) = (input.as_ref(), output.as_ref());
+        if [&self.a_pattern, &self.b_pattern]
+            .iter()
+            .any(|pattern| pattern.matches('*').count() != 1)
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "patterns must contain a single `*`",
+            ));
+        }
+
+        let mut files = vec![];
+        walk(input, &mut files)?;
+        let mut documents: HashMap<PathBuf, (Option<PathBuf>, Option<PathBuf>)> =
+            HashMap::default();
+        for file in files {
+            let Some(name) = file.file_name().and_then(|name| name.to_str()) else {
+                continue;
+            };
+            let relative = |stem: &str| {
+                let directory = file
+                    .parent()
+                    .and_then(|parent| parent.strip_prefix(input).ok());
+                directory.unwrap_or(Path::new("")).join(stem)
+            };
+            if let Some(stem) = stem(&self.a_pattern, name) {
+                documents.entry(relative(stem)).or_default().0 = Some(file.clone());
+            } else if let Some(stem) = stem(&self.b_pattern, name) {
+                documents.entry(relative(stem)).or_default().1 = Some(file.clone());
+            }
+        }
+        let mut documents = documents.into_iter().collect::<Vec<_>>();
+        documents.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+
+        let next = AtomicUsize::new(0);
+        let reports = Mutex::new(Vec::with_capacity(documents.len()));
+        let threads = self.threads.get().min(documents.len());
+        thread::scope(|scope| {
+            for _ in 0..threads {
+                scope.spawn(|| loop {
+                    let i = next.fetch_add(1, Ordering::Relaxed);
+                    let Some((name, files)) = documents.get(i) else {
+                        break;
+                    };
+                    let report = match files {
+                        (Some(a), Some(b)) => self.align(name, a, b, output),
+                        (Some(file), None) | (None, Some(file)) => DocumentReport {
+                            name: file.strip_prefix(input).unwrap_or(file).to_path_buf(),
+                            a_sentences: 0,
+                            b_sentences: 0,
+                            pairs: 0,
+                            coverage: 0.0,
+                            error: Some("unpaired".to_owned()),
+                        },
+                        (None, None) => continue,
+                    };
+                    if let Ok(mut reports) = reports.lock() {
+                        reports.push(report);
+                    }
+                });
+            }
+        });
+        let mut reports = reports.into_inner().unwrap_or_else(|e| e.into_inner());
+        reports.sort_unstable_by(|a, b| a.name.cmp(&b.name));
+
+        fs::create_dir_all(output)?;
+        let mut writer = BufWriter::new(File::create(output.join("report.tsv"))?);
+        writeln!(
+            writer,
+            "name\ta_sentences\tb_sentences\tpairs\tcoverage\terror"
+        )?;
+        for report in &reports {
+            writeln!(
+                writer,
+                "{}\t{}\t{}\t{}\t{}\t{}",
+                report.name.display(),
+                report.a_sentences,
+                report.b_sentences,
+                report.pairs,
+                report.coverage,
+                report.error.as_deref().unwrap_or_default()
+            )?;
+        }
+        writer.flush()?;
+
+        Ok(reports)
+    }
+
+    /// Aligns the files `a` and `b` of the document `name`, writing its pairs to `output`
+    fn align(&self, name: &Path, a: &Path, b: &Path, output: &Path) -> DocumentReport {
+        let mut report = DocumentReport {
+            name: name.to_path_buf(),
+            a_sentences: 0,
+            b_sentences: 0,
+            pairs: 0,
+            coverage: 0.0,
+            error: None,
+        };
+        let texts = self.read(a).and_then(|a| Ok((a, self.read(b)?)));
+        let (a, b) = match texts {
+            Ok(texts) => texts,
+            Err(error) => {
+                report.error = Some(error.to_string());
+                return report;
+            }
+        };
+        (report.a_sentences, report.b_sentences) = (a.len(), b.len());
+        if a.is_empty() || b.is_empty() {
+            report.error = Some("no sentences".to_owned());
+            return report;
+        }
+
+        // A document which panics the alignment fails on its own, keeping the worker aligning the others
+        let written = panic::catch_unwind(AssertUnwindSafe(|| {
+            let alignment = (self.config)().align(&a, &b);
+            report.pairs = alignment.pairs().count();
+            report.coverage = alignment.coverage().last().copied().unwrap_or(0.0);
+
+            let mut path = output.join(name).into_os_string();
+            path.push(".tsv");
+            let path = PathBuf::from(path);
+            if let Some(directory) = path.parent() {
+                fs::create_dir_all(directory)?;
+            }
+            let mut writer = BufWriter::new(File::create(path)?);
+            alignment.export_spans(&mut writer)?;
+            writer.flush()
+        }));
+        match written {
+            Ok(Ok(())) => {}
+            Ok(Err(error)) => report.error = Some(error.to_string()),
+            Err(_) => report.error = Some("alignment failed".to_owned()),
+        }
+
+        report
+    }
+
+    /// Returns the sentences of the lines of the file at `path`
+    fn read(&self, path: &Path) -> io::Result<Vec<Spanned<Words>>> {
+        let text = fs::read_to_string(path)?;
+        let mut sentences = vec![];
+        let mut start = 0;
+        for line in text.split_inclusive('\n') {
+            let content = line.trim_end_matches(['\n', '\r']);
+            let words = (self.segmentation)(content);
+            if !words.is_empty() {
+                sentences.push(Spanned::new(Words(words), start..start + content.len()));
+            }
+            start += line.len();
+        }
+
+        Ok(sentences)
+    }
+}
+
+struct Words(Vec<String>);
+
+impl Sentence<String> for Words {
+    fn words(&self) -> &[String] {
+        &self.0
+    }
+}
+
+/// Returns the part of the file `name` standing for the `*` of the `pattern`, if it matches
+fn stem<'n>(pattern: &str, name: &'n str) -> Option<&'n str> {
+    let (prefix, suffix) = pattern.split_once('*')?;
+    name.strip_prefix(prefix)?
+        .strip_suffix(suffix)
+        .filter(|stem| !stem.is_empty())
+}
+
+/// Collects the paths of the files of the tree of `directory`
+fn walk(directory: &Path, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in fs::read_dir(directory)? {
+        let entry = entry?;
+        if entry.file_type()?.is_dir() {
+            walk(&entry.path(), files)?;
+        } else {
+            files.push(entry.path());
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pairs_are_aligned_and_reported() {
+        let root = std::env::temp_dir().join(format!("parallelogram-batch-{}", std::process::id()));
+        let (input, output) = (root.join("input"), root.join("output"));
+        fs::create_dir_all(input.join("nested")).unwrap();
+        let text = (0..12)
+            .map(|i| format!("word{i} word{}\n", i + 1))
+            .collect::<String>();
+        fs::write(input.join("nested/one.a.txt"), &text).unwrap();
+        fs::write(input.join("nested/one.b.txt"), &text).unwrap();
+        fs::write(input.join("two.a.txt"), &text).unwrap();
+        fs::write(input.join("two.b.txt"), "\n").unwrap();
+        fs::write(input.join("three.a.txt"), &text).unwrap();
+
+        let batch = Batch {
+            config: Box::new(|| Config {
+                word_frequency_threshold: 1,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let reports = batch.run(&input, &output).unwrap();
+        let errors = reports
+            .iter()
+            .map(|report| (report.name.clone(), report.error.clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            errors,
+            [
+                (PathBuf::from("nested/one"), None),
+                (PathBuf::from("three.a.txt"), Some("unpaired".to_owned())),
+                (PathBuf::from("two"), Some("no sentences".to_owned())),
+            ]
+        );
+        assert!(reports[0].pairs > 0);
+        let pairs = fs::read_to_string(output.join("nested/one.tsv")).unwrap();
+        assert_eq!(pairs.lines().count(), reports[0].pairs);
+        let report = fs::read_to_string(output.join("report.tsv")).unwrap();
+        assert_eq!(report.lines().count(), 4);
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,410 @@
+//! Compact binary format for outputs, checkpoints and lexicons, far smaller and faster to read and write
+//! than text dumps of alignments of millions of pairs. Each file starts with the magic bytes `PGRM`, the
+//! version of the format and the kind of its contents. Sentence indices are written as little endian
+//! `u32`, scores and counts as LEB128 varints and probabilities as little endian `f32`, as in postcard
+
+use std::io::{self, Read, Write};
+
+use super::sentence_alignment_table::Score;
+use super::shared::Text;
+use super::{
+    translations, Abridgement, AlignedPair, Coordinates, Output, Sentence, SentenceAlignmentTable,
+    X, Y,
+};
+
+/// Version of the format written, the only version read
+pub const VERSION: u8 = 1;
+
+const MAGIC: &[u8; 4] = b"PGRM";
+
+/// Kind of the contents of a file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Output = 0,
+    Checkpoint = 1,
+    Lexicon = 2,
+}
+
+/// A translation read from a lexicon written by `Output::write_translations_binary`
+#[derive(Debug, Clone, PartialEq)]
+pub struct LexiconEntry {
+    pub a: String,
+    pub b: String,
+    /// Number of aligned pairs in which both words occur
+    pub count: usize,
+    /// Probability of `b` translating `a`
+    pub probability: f32,
+}
+
+/// Writes the `cells` as a checkpoint, from which an alignment may be resumed with `Config::align_from`
+pub fn write_cells(
+    mut writer: impl Write,
+    cells: impl IntoIterator<Item = AlignedPair>,
+) -> io::Result<()> {
+    let cells = cells.into_iter().collect::<Vec<_>>();
+    write_header(&mut writer, Kind::Checkpoint)?;
+    write_varint(&mut writer, cells.len())?;
+    for pair in cells {
+        write_pair(&mut writer, (pair.a, pair.b))?;
+        write_varint(&mut writer, pair.score)?;
+    }
+
+    Ok(())
+}
+
+/// Reads the cells of a checkpoint written by `write_cells`
+pub fn read_cells(mut reader: impl Read) -> io::Result<Vec<AlignedPair>> {
+    read_header(&mut reader, Kind::Checkpoint)?;
+    (0..read_varint(&mut reader)?)
+        .map(|_| {
+            let (a, b) = read_pair(&mut reader)?;
+            let score = read_varint(&mut reader)?;
+            Ok(AlignedPair { a, b, score })
+        })
+        .collect()
+}
+
+/// Reads the translations of a lexicon written by `Output::write_translations_binary`
+pub fn read_lexicon(mut reader: impl Read) -> io::Result<Vec<LexiconEntry>> {
+    read_header(&mut reader, Kind::Lexicon)?;
+    (0..read_varint(&mut reader)?)
+        .map(|_| {
+            Ok(LexiconEntry {
+                a: read_string(&mut reader)?,
+                b: read_string(&mut reader)?,
+                count: read_varint(&mut reader)?,
+                probability: read_f32(&mut reader)?,
+            })
+        })
+        .collect()
+}
+
+impl<'a, T, U> Output<'a, T, U> {
+    /// Writes the alignment to `writer` in the binary format: the scores of the SAT with its locked and
+    /// forbidden pairs, the coverage, the abridgement, the outliers, the boilerplate and the translations.
+    /// The snapshots, provenance, moves and quality are not written
+    pub fn write_binary(&self, mut writer: impl Write) -> io::Result<()> {
+        let writer = &mut writer;
+        write_header(writer, Kind::Output)?;
+        write_index(writer, self.a.len())?;
+        write_index(writer, self.b.len())?;
+
+        write_varint(writer, self.sat.anchor_threshold().0)?;
+        let cells = self.sat.cells().collect::<Vec<_>>();
+        write_varint(writer, cells.len())?;
+        for (Coordinates(X(x), Y(y)), score) in cells {
+            write_pair(writer, (y, x))?;
+            write_varint(writer, score.0)?;
+        }
+        write_coordinates(writer, self.sat.locked())?;
+        write_coordinates(writer, self.sat.forbidden())?;
+
+        write_varint(writer, self.coverage.len())?;
+        for &coverage in &self.coverage {
+            write_f32(writer, coverage)?;
+        }
+        match self.abridgement {
+            Some(abridgement) => {
+                writer.write_all(&[1])?;
+                write_f32(writer, abridgement.deletion_rate)?;
+                write_varint(writer, abridgement.min_skip)?;
+            }
+            None => writer.write_all(&[0])?,
+        }
+        write_varint(writer, self.outliers.len())?;
+        for &pair in &self.outliers {
+            write_pair(writer, pair)?;
+        }
+        for boilerplate in [&self.boilerplate.0, &self.boilerplate.1] {
+            write_varint(writer, boilerplate.len())?;
+            for &i in boilerplate {
+                write_index(writer, i)?;
+            }
+        }
+        write_varint(writer, self.translations.len())?;
+        for entry in &self.translations {
+            write_pair(writer, entry.a)?;
+            write_pair(writer, entry.b)?;
+            write_varint(writer, entry.count)?;
+            write_f32(writer, entry.probability)?;
+        }
+
+        Ok(())
+    }
+
+    /// Reads an alignment of the texts `a` and `b` written by `write_binary`, failing if it is not an
+    /// alignment of texts of their lengths
+    pub fn read_binary<Word>(a: &'a [T], b: &'a [U], mut reader: impl Read) -> io::Result<Self>
+    where
+        Word: PartialEq,
+        T: Sentence<Word>,
+        U: Sentence<Word>,
+    {
+        let reader = &mut reader;
+        read_header(reader, Kind::Output)?;
+        if read_index(reader)? != a.len()
+            || read_index(reader)? != b.len()
+            || a.is_empty()
+            || b.is_empty()
+        {
+            return Err(invalid(
+                "the lengths of the texts differ from those aligned",
+            ));
+        }
+        let in_texts = |(y, x): (usize, usize)| {
+            if y < a.len() && x < b.len() {
+                Ok(Coordinates(X(x), Y(y)))
+            } else {
+                Err(invalid("pair outside of the texts"))
+            }
+        };
+
+        let mut sat = SentenceAlignmentTable::new(a, b, Score(read_varint(reader)?));
+        for _ in 0..read_varint(reader)? {
+            let coordinates = in_texts(read_pair(reader)?)?;
+            sat.add(coordinates, Score(read_varint(reader)?));
+        }
+        for _ in 0..read_varint(reader)? {
+            sat.lock(in_texts(read_pair(reader)?)?);
+        }
+        for _ in 0..read_varint(reader)? {
+            sat.forbid(in_texts(read_pair(reader)?)?);
+        }
+
+        let coverage = (0..read_varint(reader)?)
+            .map(|_| read_f32(reader))
+            .collect::<io::Result<_>>()?;
+        let abridgement = match read_byte(reader)? {
+            0 => None,
+            _ => Some(Abridgement {
+                deletion_rate: read_f32(reader)?,
+                min_skip: read_varint(reader)?,
+            }),
+        };
+        let outliers = (0..read_varint(reader)?)
+            .map(|_| read_pair(reader))
+            .collect::<io::Result<_>>()?;
+        let mut boilerplate = (vec![], vec![]);
+        for (boilerplate, len) in [(&mut boilerplate.0, a.len()), (&mut boilerplate.1, b.len())] {
+            for _ in 0..read_varint(reader)? {
+                match read_index(reader)? {
+                    i if i < len => boilerplate.push(i),
+                    _ => return Err(invalid("sentence outside of the texts")),
+                }
+            }
+        }
+        let translations = (0..read_varint(reader)?)
+            .map(|_| {
+                Ok(translations::Entry {
+                    a: read_pair(reader)?,
+                    b: read_pair(reader)?,
+                    count: read_varint(reader)?,
+                    probability: read_f32(reader)?,
+                })
+            })
+            .collect::<io::Result<Vec<_>>>()?;
+        let in_sentence = |sentence: Option<&[Word]>, offset: usize| {
+            sentence.is_some_and(|words| offset < words.len())
+        };
+        if !translations.iter().all(|entry| {
+            in_sentence(a.get(entry.a.0).map(Sentence::words), entry.a.1)
+                && in_sentence(b.get(entry.b.0).map(Sentence::words), entry.b.1)
+        }) {
+            return Err(invalid("translation outside of the texts"));
+        }
+
+        let mut output = Output::new(
+            Text::Borrowed(a),
+            Text::Borrowed(b),
+            sat,
+            coverage,
+            abridgement,
+            outliers,
+            vec![],
+            Default::default(),
+        );
+        output.boilerplate = boilerplate;
+        output.translations = translations;
+
+        Ok(output)
+    }
+
+    /// Writes the cells of the SAT to `writer` as a checkpoint, from which the alignment may be resumed
+    /// with `Config::align_from`
+    pub fn write_checkpoint(&self, writer: impl Write) -> io::Result<()> {
+        write_cells(writer, self.cells())
+    }
+
+    /// Writes the `translations` to `writer` as a lexicon, read by `read_lexicon`
+    pub fn write_translations_binary<Word>(&self, mut writer: impl Write) -> io::Result<()>
+    where
+        Word: PartialEq + AsRef<str>,
+        T: Sentence<Word>,
+        U: Sentence<Word>,
+    {
+        let translations = self.translations();
+        write_header(&mut writer, Kind::Lexicon)?;
+        write_varint(&mut writer, translations.len())?;
+        for translation in translations {
+            write_string(&mut writer, translation.a.as_ref())?;
+            write_string(&mut writer, translation.b.as_ref())?;
+            write_varint(&mut writer, translation.count)?;
+            write_f32(&mut writer, translation.probability)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+fn write_header(writer: &mut impl Write, kind: Kind) -> io::Result<()> {
+    writer.write_all(MAGIC)?;
+    writer.write_all(&[VERSION, kind as u8])
+}
+
+fn read_header(reader: &mut impl Read, kind: Kind) -> io::Result<()> {
+    let mut header = [0; 6];
+    reader.read_exact(&mut header)?;
+    if &header[..4] != MAGIC {
+        Err(invalid("not a binary alignment file"))
+    } else if header[4] != VERSION {
+        Err(invalid("unsupported version of the format"))
+    } else if header[5] != kind as u8 {
+        Err(invalid("unexpected kind of contents"))
+    } else {
+        Ok(())
+    }
+}
+
+fn write_varint(writer: &mut impl Write, mut value: usize) -> io::Result<()> {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            return writer.write_all(&[byte]);
+        }
+        writer.write_all(&[byte | 0x80])?;
+    }
+}
+
+fn read_varint(reader: &mut impl Read) -> io::Result<usize> {
+    let mut value = 0usize;
+    for shift in (0..usize::BITS).step_by(7) {
+        let byte = read_byte(reader)?;
+        let payload = usize::from(byte & 0x7f);
+        // The bits of the payload beyond the width of `usize` would be lost
+        if payload >> (usize::BITS - shift).min(7) != 0 {
+            return Err(invalid("varint overflow"));
+        }
+        value |= payload << shift;
+        if byte & 0x80 == 0 {
+            // `write_varint` never ends a varint on a zero byte after the first
+            if byte == 0 && shift > 0 {
+                return Err(invalid("overlong varint"));
+            }
+            return Ok(value);
+        }
+    }
+
+    Err(invalid("varint overflow"))
+}
+
+fn read_byte(reader: &mut impl Read) -> io::Result<u8> {
+    let mut byte = [0];
+    reader.read_exact(&mut byte)?;
+    Ok(byte[0])
+}
+
+fn write_index(writer: &mut impl Write, index: usize) -> io::Result<()> {
+    let index = u32::try_from(index)
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "index exceeds u32"))?;
+    writer.write_all(&index.to_le_bytes())
+}
+
+fn read_index(reader: &mut impl Read) -> io::Result<usize> {
+    let mut bytes = [0; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes) as usize)
+}
+
+fn write_pair(writer: &mut impl Write, (a, b): (usize, usize)) -> io::Result<()> {
+    write_index(writer, a)?;
+    write_index(writer, b)
+}
+
+fn read_pair(reader: &mut impl Read) -> io::Result<(usize, usize)> {
+    Ok((read_index(reader)?, read_index(reader)?))
+}
+
+/// Writes the `coordinates` as pairs of sentence indices `(a, b)`, sorted so that the output is stable
+fn write_coordinates(
+    writer: &mut impl Write,
+    coordinates: impl Iterator<Item = Coordinates>,
+) -> io::Result<()> {
+    let mut pairs = coordinates
+        .map(|Coordinates(X(x), Y(y))| (y, x))
+        .collect::<Vec<_>>();
+    pairs.sort_unstable();
+    write_varint(writer, pairs.len())?;
+    for pair in pairs {
+        write_pair(writer, pair)?;
+    }
+
+    Ok(())
+}
+
+fn write_f32(writer: &mut impl Write, value: f32) -> io::Result<()> {
+    writer.write_all(&value.to_le_bytes())
+}
+
+fn read_f32(reader: &mut impl Read) -> io::Result<f32> {
+    let mut bytes = [0; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(f32::from_le_bytes(bytes))
+}
+
+fn write_string(writer: &mut impl Write, value: &str) -> io::Result<()> {
+    write_varint(writer, value.len())?;
+    writer.write_all(value.as_bytes())
+}
+
+fn read_string(reader: &mut impl Read) -> io::Result<String> {
+    let len = read_varint(reader)?;
+    let mut bytes = vec![];
+    reader.take(len as u64).read_to_end(&mut bytes)?;
+    if bytes.len() < len {
+        return Err(io::ErrorKind::UnexpectedEof.into());
+    }
+
+    String::from_utf8(bytes).map_err(|_| invalid("invalid UTF-8"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode(bytes: &[u8]) -> io::Result<usize> {
+        read_varint(&mut &bytes[..])
+    }
+
+    #[test]
+    fn varint_round_trips() {
+        for value in [0, 1, 127, 128, 300, u32::MAX as usize, usize::MAX] {
+            let mut bytes = vec![];
+            write_varint(&mut bytes, value).unwrap();
+            assert_eq!(decode(&bytes).unwrap(), value);
+        }
+    }
+
+    #[test]
+    fn varint_rejects_lost_bits_and_overlong_encodings() {
+        let mut overflowing = vec![0xff; usize::BITS as usize / 7];
+        overflowing.push(0x02);
+        assert!(decode(&overflowing).is_err());
+        assert!(decode(&[0x80, 0x00]).is_err());
+        assert!(decode(&[0x00]).is_ok());
+    }
+}
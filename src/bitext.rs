@@ -0,0 +1,41 @@
+use std::hash::Hash;
+
+use super::{Config, Output, Sentence};
+
+/// Texts collected from iterators of sentences, e.g. streamed from a parser or a database cursor, which
+/// the `Output` of their alignment borrows from
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bitext<T, U> {
+    a: Vec<T>,
+    b: Vec<U>,
+}
+
+impl<T, U> Bitext<T, U> {
+    /// Collects the sentences of texts `a` and `b`
+    pub fn new(a: impl IntoIterator<Item = T>, b: impl IntoIterator<Item = U>) -> Self {
+        Self {
+            a: a.into_iter().collect(),
+            b: b.into_iter().collect(),
+        }
+    }
+
+    /// Returns the sentences of text `a`
+    pub fn a(&self) -> &[T] {
+        &self.a
+    }
+
+    /// Returns the sentences of text `b`
+    pub fn b(&self) -> &[U] {
+        &self.b
+    }
+
+    /// Aligns the texts with the `config`, see `Config::align`
+    pub fn align<'a, Word>(&'a self, config: Config<Word>) -> Output<'a, T, U>
+    where
+        Word: Eq + Hash + 'a,
+        T: Sentence<Word>,
+        U: Sentence<Word>,
+    {
+        config.align(&self.a, &self.b)
+    }
+}
@@ -0,0 +1,144 @@
+use std::cmp::Reverse;
+use std::collections::HashSet;
+use std::hash::Hash;
+use std::ops::Range;
+
+use super::lexicon::Lexicon;
+use super::{AlignedPair, Output, Sentence, WordSentenceIndex, X, Y};
+
+/// Detection of sections moved from one position to another between the texts, such as appendices
+/// moved or chapters reordered, which the monotone alignment cannot represent
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BlockMoves {
+    /// Number of sentences of the windows of text `a` whose evidence is compared to that of the windows of
+    /// text `b`, and so the minimum length of a move. Defaults to `5`
+    pub min_len: usize,
+    /// Fraction of the distinct words of a window of text `a` which must have a partner in a window of text
+    /// `b`, among the word associations accepted while aligning, for the windows to be considered the same
+    /// section. Defaults to `0.3`
+    pub min_density: f32,
+    /// Fraction of the sentences of a candidate move which must be aligned to one another when the move is
+    /// aligned independently for it to be reported. Defaults to `0.5`
+    pub min_coverage: f32,
+}
+
+impl Default for BlockMoves {
+    fn default() -> Self {
+        Self {
+            min_len: 5,
+            min_density: 0.3,
+            min_coverage: 0.5,
+        }
+    }
+}
+
+impl BlockMoves {
+    /// Returns the candidate moves as the ranges `(a, b)` of their sentences, densest first. Each window of
+    /// text `a` is compared to the windows of text `b` through the `lexicon`, and a window whose densest
+    /// counterpart lies away from the sentences it was aligned with (given by `aligned`) is off-diagonal.
+    /// Consecutive off-diagonal windows whose counterparts are consecutive form a single candidate
+    pub(crate) fn candidates<'a, Word, T>(
+        &self,
+        a: &'a [T],
+        b_len: usize,
+        a_word_sentence_index: &WordSentenceIndex<'a, Word, Y>,
+        b_word_sentence_index: &WordSentenceIndex<'a, Word, X>,
+        lexicon: &Lexicon<'a, Word>,
+        aligned: impl Fn(&Range<usize>) -> Option<Range<usize>>,
+    ) -> Vec<(Range<usize>, Range<usize>)>
+    where
+        Word: Eq + Hash,
+        T: Sentence<Word>,
+    {
+        let len = self.min_len.max(2);
+        let step = (len / 2).max(1);
+        if a.len() < len || b_len < len {
+            return vec![];
+        }
+        let b_windows = (b_len - len) / step + 1;
+
+        let mut seeds: Vec<(f32, Range<usize>, Range<usize>)> = vec![];
+        for a_start in (0..=a.len() - len).step_by(len) {
+            let a_range = a_start..a_start + len;
+            let words = a[a_range.clone()]
+                .iter()
+                .flat_map(|sentence| sentence.words())
+                .filter_map(|word| a_word_sentence_index.indexed(word))
+                .collect::<HashSet<_>>();
+
+            let mut support = vec![0; b_windows];
+            for word in &words {
+                let windows = lexicon
+                    .get(word)
+                    .into_iter()
+                    .flatten()
+                    .flat_map(|partner| b_word_sentence_index.sentences(partner))
+                    .flat_map(|X(j)| {
+                        // Windows starting at most `len - 1` sentences before `j`
+                        (j + 1).saturating_sub(len).div_ceil(step)..(j / step + 1).min(b_windows)
+                    })
+                    .collect::<HashSet<_>>();
+                for window in windows {
+                    support[window] += 1;
+                }
+            }
+
+            let Some((best, &count)) = support
+                .iter()
+                .enumerate()
+                .max_by_key(|&(i, &count)| (count, Reverse(i)))
+            else {
+                continue;
+            };
+            let density = count as f32 / words.len().max(1) as f32;
+            let b_range = best * step..best * step + len;
+            let off_diagonal = aligned(&a_range).is_none_or(|aligned| {
+                let near = aligned.start.saturating_sub(len)..aligned.end + len;
+                !overlaps(&near, &b_range)
+            });
+            if density >= self.min_density && off_diagonal {
+                seeds.push((density, a_range, b_range));
+            }
+        }
+
+        let mut candidates: Vec<(f32, Range<usize>, Range<usize>)> = vec![];
+        for (density, a_range, b_range) in seeds {
+            match candidates.last_mut() {
+                Some((total, a, b))
+                    if a.end == a_range.start && b_range.start.abs_diff(b.end) <= len =>
+                {
+                    *total += density;
+                    a.end = a_range.end;
+                    b.end = b.end.max(b_range.end);
+                }
+                _ => candidates.push((density, a_range, b_range)),
+            }
+        }
+        candidates.sort_by(|(a, ..), (b, ..)| b.total_cmp(a));
+
+        candidates.into_iter().map(|(_, a, b)| (a, b)).collect()
+    }
+}
+
+fn overlaps(a: &Range<usize>, b: &Range<usize>) -> bool {
+    a.start < b.end && b.start < a.end
+}
+
+/// A section aligned independently of the rest of the texts, found at different positions in each text
+#[derive(Debug, Clone, PartialEq)]
+pub struct Move {
+    /// Range of the sentences of the section in text `a`
+    pub a: Range<usize>,
+    /// Range of the sentences of the section in text `b`
+    pub b: Range<usize>,
+    /// Pairs aligned within the section, ordered by `a`
+    pub pairs: Vec<AlignedPair>,
+}
+
+impl<'a, T, U> Output<'a, T, U> {
+    /// Returns the sections aligned independently of the rest of the texts, in the order of text `a`.
+    /// Always empty unless the texts were aligned with `BlockMoves`
+    pub fn moves(&self) -> &[Move] {
+        &self.moves
+    }
+}
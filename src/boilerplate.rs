@@ -0,0 +1,157 @@
+use std::hash::Hash;
+
+use super::hash::{HashMap, HashSet};
+use super::{Coordinates, Output, Sentence, SentenceAlignmentTable, X, Y};
+
+/// Handling of the sentences repeated verbatim within a text, such as copyright notices or separators.
+/// Their copies are each as good a candidate as the others, defeating the requirement of a unique
+/// candidate, and their words inflate the frequencies of the rest of the text. The copies excluded from the
+/// index are not counted towards the coverage
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Boilerplate {
+    /// Minimum number of identical copies of a sentence within a text for it to be boilerplate.
+    /// Defaults to `3`
+    pub min_repeats: usize,
+    /// Treatment of the boilerplate. Defaults to `BoilerplatePolicy::Positional`
+    pub policy: BoilerplatePolicy,
+}
+
+impl Default for Boilerplate {
+    fn default() -> Self {
+        Self {
+            min_repeats: 3,
+            policy: BoilerplatePolicy::Positional,
+        }
+    }
+}
+
+/// Treatment of the boilerplate of the texts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BoilerplatePolicy {
+    /// Index only the first copy of each repeated sentence, so that its words count once
+    Collapse,
+    /// Exclude every copy from the index, so that the boilerplate provides no evidence
+    Exclude,
+    /// Exclude every copy from the index, then align the copies left unaligned once the cycles have
+    /// finished by their order between the surrounding anchors, where each text has as many copies
+    Positional,
+}
+
+impl Boilerplate {
+    /// Returns the indices of the boilerplate sentences of `text` in order, with those of the sentences
+    /// excluded from the index under the policy
+    pub(crate) fn detect<Word, S>(&self, text: &[S]) -> (Vec<usize>, HashSet<usize>)
+    where
+        Word: Eq + Hash,
+        S: Sentence<Word>,
+    {
+        let mut copies: HashMap<&[Word], Vec<usize>> = HashMap::default();
+        for (i, sentence) in text.iter().enumerate() {
+            if !sentence.words().is_empty() {
+                copies.entry(sentence.words()).or_default().push(i);
+            }
+        }
+
+        let mut repeated = vec![];
+        let mut excluded = HashSet::default();
+        for copies in copies.into_values() {
+            if copies.len() < self.min_repeats.max(2) {
+                continue;
+            }
+            excluded.extend(match self.policy {
+                // The first copy of each sentence is kept in the index
+                BoilerplatePolicy::Collapse => &copies[1..],
+                BoilerplatePolicy::Exclude | BoilerplatePolicy::Positional => &copies[..],
+            });
+            repeated.extend(copies);
+        }
+        repeated.sort_unstable();
+
+        (repeated, excluded)
+    }
+
+    /// Returns the words of the sentences of `text` under which they are indexed, with no words for the
+    /// `excluded` sentences
+    pub(crate) fn indexed<'a: 'b, 'b, Word, S>(
+        text: &'a [S],
+        excluded: &'b HashSet<usize>,
+    ) -> impl Iterator<Item = &'a [Word]> + 'b
+    where
+        Word: PartialEq + 'a,
+        S: Sentence<Word>,
+    {
+        text.iter().enumerate().map(move |(i, sentence)| {
+            if excluded.contains(&i) {
+                &[]
+            } else {
+                sentence.words()
+            }
+        })
+    }
+
+    /// Anchors the unaligned boilerplate sentences lying between the same consecutive anchors of the SAT
+    /// wherever both texts have as many of them, in order, returning them
+    pub(crate) fn align_by_position(
+        &self,
+        sat: &mut SentenceAlignmentTable,
+        a_boilerplate: &[usize],
+        b_boilerplate: &[usize],
+        a_len: usize,
+        b_len: usize,
+    ) -> Vec<Coordinates> {
+        if self.policy != BoilerplatePolicy::Positional {
+            return vec![];
+        }
+
+        let mut anchors = sat.anchors().collect::<Vec<_>>();
+        anchors.sort_unstable_by_key(|&Coordinates(x, y)| (y, x));
+        let a_aligned = anchors.iter().map(|c| c.y().0).collect::<HashSet<_>>();
+        let b_aligned = anchors.iter().map(|c| c.x().0).collect::<HashSet<_>>();
+        let between =
+            |boilerplate: &[usize], aligned: &HashSet<usize>, start: usize, end: usize| {
+                boilerplate[boilerplate.partition_point(|&i| i < start)..]
+                    .iter()
+                    .take_while(|&&i| i < end)
+                    .filter(|i| !aligned.contains(i))
+                    .copied()
+                    .collect::<Vec<_>>()
+            };
+
+        let mut aligned = vec![];
+        let mut start = (0, 0);
+        let ends = anchors
+            .iter()
+            .map(|&Coordinates(X(x), Y(y))| (y, x))
+            .chain([(a_len, b_len)]);
+        for (y, x) in ends {
+            // Anchors crossing the previous one leave no gap between them
+            if y < start.0 || x < start.1 {
+                continue;
+            }
+
+            let a_gap = between(a_boilerplate, &a_aligned, start.0, y);
+            let b_gap = between(b_boilerplate, &b_aligned, start.1, x);
+            if !a_gap.is_empty() && a_gap.len() == b_gap.len() {
+                for (y, x) in a_gap.into_iter().zip(b_gap) {
+                    let coordinates = Coordinates(X(x), Y(y));
+                    if !sat.is_forbidden(coordinates) && !sat.crosses_locked(coordinates) {
+                        sat.anchor(coordinates);
+                        aligned.push(coordinates);
+                    }
+                }
+            }
+
+            start = (y + 1, x + 1);
+        }
+
+        aligned
+    }
+}
+
+impl<'a, T, U> Output<'a, T, U> {
+    /// Returns the indices of the sentences of texts `a` and `b` treated as boilerplate. Always empty
+    /// unless the texts were aligned with `Boilerplate`
+    pub fn boilerplate(&self) -> (&[usize], &[usize]) {
+        (&self.boilerplate.0, &self.boilerplate.1)
+    }
+}
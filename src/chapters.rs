@@ -0,0 +1,252 @@
+use std::hash::Hash;
+use std::num::NonZeroUsize;
+use std::ops::Range;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+use super::hash::HashMap;
+use super::{
+    translations, AlignedPair, Config, Coordinates, Output, Sentence, SentenceAlignmentTable,
+    Snapshot, Text, X, Y,
+};
+
+/// Corresponding chapters of the texts, e.g. found from their headings or by matching their paragraphs,
+/// each pair of which is aligned independently of the others on its own thread. Sentences are only
+/// aligned within their chapter, so the work grows with the length of the chapters rather than of the
+/// texts
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Chapters {
+    a: Vec<usize>,
+    b: Vec<usize>,
+}
+
+impl Chapters {
+    /// Returns the chapters starting at the given indices of the sentences of texts `a` and `b`, each
+    /// chapter spanning until the start of the next, and the first chapter also including the sentences
+    /// before its start. Returns `None` unless both texts have as many chapters, with increasing starts
+    pub fn new(a_starts: Vec<usize>, b_starts: Vec<usize>) -> Option<Self> {
+        let increasing = |starts: &[usize]| starts.windows(2).all(|pair| pair[0] < pair[1]);
+        (!a_starts.is_empty()
+            && a_starts.len() == b_starts.len()
+            && increasing(&a_starts)
+            && increasing(&b_starts))
+        .then_some(Self {
+            a: a_starts,
+            b: b_starts,
+        })
+    }
+
+    /// Returns the ranges of the sentences of each chapter of texts `a` and `b` of the given lengths
+    pub fn ranges(&self, a_len: usize, b_len: usize) -> Vec<(Range<usize>, Range<usize>)> {
+        let ranges = |starts: &[usize], len: usize| {
+            (0..starts.len())
+                .map(|i| {
+                    let start = if i == 0 { 0 } else { starts[i].min(len) };
+                    let end = starts.get(i + 1).map_or(len, |&end| end.min(len));
+                    start..end
+                })
+                .collect::<Vec<_>>()
+        };
+
+        ranges(&self.a, a_len)
+            .into_iter()
+            .zip(ranges(&self.b, b_len))
+            .collect()
+    }
+
+    /// Aligns each pair of chapters with the configuration returned by `config`, on as many threads as
+    /// are available, and merges their alignments into a single output indexing the whole texts. The
+    /// indices of the configuration (e.g. the `locked_anchors`) are relative to each chapter, and the
+    /// coverage of each cycle is that of the texts, counting a chapter whose cycles have finished at its
+    /// final coverage. Chapters are much shorter than the texts, so thresholds derived from their
+    /// vocabulary with `Config::frequency_scaling` suit them better than fixed ones
+    pub fn align<'a, Word, T, U>(
+        &self,
+        a: &'a [T],
+        b: &'a [U],
+        config: impl Fn() -> Config<Word> + Sync,
+    ) -> Output<'a, T, U>
+    where
+        Word: Eq + Hash + 'a,
+        T: Sentence<Word> + Send + Sync,
+        U: Sentence<Word> + Send + Sync,
+    {
+        // Chapters empty in either text have nothing to align
+        let chapters = self
+            .ranges(a.len(), b.len())
+            .into_iter()
+            .filter(|(a_range, b_range)| !a_range.is_empty() && !b_range.is_empty())
+            .collect::<Vec<_>>();
+
+        let next = AtomicUsize::new(0);
+        let outputs = Mutex::new(Vec::with_capacity(chapters.len()));
+        let threads = thread::available_parallelism()
+            .map_or(1, NonZeroUsize::get)
+            .min(chapters.len());
+        thread::scope(|scope| {
+            for _ in 0..threads {
+                scope.spawn(|| loop {
+                    let i = next.fetch_add(1, Ordering::Relaxed);
+                    let Some((a_range, b_range)) = chapters.get(i) else {
+                        break;
+                    };
+                    let output = config().align(&a[a_range.clone()], &b[b_range.clone()]);
+                    if let Ok(mut outputs) = outputs.lock() {
+                        outputs.push((i, output));
+                    }
+                });
+            }
+        });
+        let mut outputs = outputs.into_inner().unwrap_or_else(|e| e.into_inner());
+        outputs.sort_unstable_by_key(|&(i, _)| i);
+
+        merge(
+            a,
+            b,
+            chapters
+                .into_iter()
+                .zip(outputs)
+                .map(|((a_range, b_range), (_, output))| (a_range.start, b_range.start, output)),
+        )
+    }
+}
+
+/// Returns the output of the whole texts from the outputs of their chapters, given with the indices of
+/// their first sentences
+fn merge<'a, Word, T, U>(
+    a: &'a [T],
+    b: &'a [U],
+    chapters: impl IntoIterator<Item = (usize, usize, Output<'a, T, U>)>,
+) -> Output<'a, T, U>
+where
+    Word: Eq + Hash + 'a,
+    T: Sentence<Word>,
+    U: Sentence<Word>,
+{
+    let chapters = chapters.into_iter().collect::<Vec<_>>();
+    let anchor_threshold = chapters.first().map_or(Default::default(), |(.., output)| {
+        output.sat.anchor_threshold()
+    });
+    let table = |tables: Vec<&SentenceAlignmentTable>| {
+        let mut sat = SentenceAlignmentTable::new(a, b, anchor_threshold);
+        for ((a_start, b_start, _), table) in chapters.iter().zip(tables) {
+            sat.merge(table, Coordinates(X(*b_start), Y(*a_start)));
+        }
+        sat
+    };
+
+    let sat = table(chapters.iter().map(|(.., output)| &output.sat).collect());
+    let cycles = chapters
+        .iter()
+        .map(|(.., output)| output.coverage.len())
+        .max()
+        .unwrap_or_default();
+    let snapshots = (0..chapters
+        .iter()
+        .map(|(.., output)| output.snapshots.len())
+        .max()
+        .unwrap_or_default())
+        .map(|cycle| {
+            // Chapters which finished in fewer cycles remain as they were at their last cycle
+            let sat = table(
+                chapters
+                    .iter()
+                    .map(|(.., output)| {
+                        output
+                            .snapshots
+                            .get(cycle)
+                            .or(output.snapshots.last())
+                            .map_or(&output.sat, Snapshot::sat)
+                    })
+                    .collect(),
+            );
+            Snapshot::new(cycle, &sat)
+        })
+        .collect();
+    let coverage = (0..cycles)
+        .map(|cycle| {
+            let (covered, total) =
+                chapters
+                    .iter()
+                    .fold((0.0, 0), |(covered, total), (.., output)| {
+                        let len = output.a.len() + output.b.len();
+                        let coverage = output
+                            .coverage
+                            .get(cycle)
+                            .or(output.coverage.last())
+                            .copied()
+                            .unwrap_or_default();
+                        (covered + coverage * len as f32, total + len)
+                    });
+            covered / total.max(1) as f32
+        })
+        .collect();
+
+    let offset = |a_start: usize, b_start: usize| {
+        move |Coordinates(X(x), Y(y)): Coordinates| Coordinates(X(x + b_start), Y(y + a_start))
+    };
+    let mut outliers = vec![];
+    let mut provenance = HashMap::default();
+    let mut moves = vec![];
+    let mut quality = vec![];
+    let mut boilerplate = (vec![], vec![]);
+    let mut translations = vec![];
+    for (a_start, b_start, output) in &chapters {
+        let (a_start, b_start) = (*a_start, *b_start);
+        let offset = offset(a_start, b_start);
+        outliers.extend(
+            output
+                .outliers
+                .iter()
+                .map(|&(a, b)| (a + a_start, b + b_start)),
+        );
+        provenance.extend(
+            output
+                .provenance
+                .iter()
+                .map(|(&coordinates, evidence)| (offset(coordinates), evidence.clone())),
+        );
+        moves.extend(output.moves.iter().cloned().map(|mut m| {
+            m.a = m.a.start + a_start..m.a.end + a_start;
+            m.b = m.b.start + b_start..m.b.end + b_start;
+            for pair in &mut m.pairs {
+                *pair = AlignedPair {
+                    a: pair.a + a_start,
+                    b: pair.b + b_start,
+                    score: pair.score,
+                };
+            }
+            m
+        }));
+        quality.extend(output.quality.iter().cloned().map(|mut pair| {
+            pair.a += a_start;
+            pair.b += b_start;
+            pair
+        }));
+        boilerplate
+            .0
+            .extend(output.boilerplate.0.iter().map(|i| i + a_start));
+        boilerplate
+            .1
+            .extend(output.boilerplate.1.iter().map(|i| i + b_start));
+        translations.push((a_start, b_start, output.translations.clone()));
+    }
+
+    let mut output = Output::new(
+        Text::Borrowed(a),
+        Text::Borrowed(b),
+        sat,
+        coverage,
+        chapters.first().and_then(|(.., output)| output.abridgement),
+        outliers,
+        snapshots,
+        provenance,
+    );
+    output.moves = moves;
+    output.quality = quality;
+    output.boilerplate = boilerplate;
+    output.translations = translations::merge(a, b, translations);
+
+    output
+}
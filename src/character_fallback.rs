@@ -0,0 +1,106 @@
+use std::collections::HashSet;
+
+use super::{Coordinates, Sentence, X, Y};
+
+type Characters<Word> = Box<dyn Fn(&Word) -> String>;
+
+/// Fallback for texts too short for word frequencies to clear the WAT thresholds. Sentence pairs are
+/// scored directly by the overlap of their character n-grams, and the best scoring monotone sequence
+/// of pairs is used to seed the SAT with anchors before the first cycle
+pub struct CharacterFallback<Word> {
+    /// Length of the character n-grams compared between sentences. Defaults to `3`
+    pub n: usize,
+    /// Dice coefficient of the n-gram sets required for a pair to seed an anchor. Defaults to `0.5`
+    pub similarity_threshold: f32,
+    /// The fallback is only used when both texts have at most this many sentences. Defaults to `50`
+    pub max_sentences: usize,
+    /// Characters of a word from which the n-grams are taken. Defaults to the word itself
+    pub characters: Characters<Word>,
+}
+
+impl<Word: AsRef<str>> Default for CharacterFallback<Word> {
+    fn default() -> Self {
+        Self {
+            n: 3,
+            similarity_threshold: 0.5,
+            max_sentences: 50,
+            characters: Box::new(|word| word.as_ref().to_owned()),
+        }
+    }
+}
+
+impl<Word> CharacterFallback<Word> {
+    pub(crate) fn seeds<T, U>(&self, a: &[T], b: &[U]) -> Vec<Coordinates>
+    where
+        Word: PartialEq,
+        T: Sentence<Word>,
+        U: Sentence<Word>,
+    {
+        if a.len() > self.max_sentences || b.len() > self.max_sentences {
+            return vec![];
+        }
+
+        let a_ngrams = a
+            .iter()
+            .map(|sentence| self.ngrams(sentence.words()))
+            .collect::<Vec<_>>();
+        let b_ngrams = b
+            .iter()
+            .map(|sentence| self.ngrams(sentence.words()))
+            .collect::<Vec<_>>();
+
+        // Maximum weight monotone path through the pairs exceeding the similarity threshold
+        let mut best = vec![vec![0.0f32; b.len() + 1]; a.len() + 1];
+        for (y, a_ngrams) in a_ngrams.iter().enumerate() {
+            for (x, b_ngrams) in b_ngrams.iter().enumerate() {
+                let diagonal = match dice(a_ngrams, b_ngrams) {
+                    similarity if similarity >= self.similarity_threshold => {
+                        best[y][x] + similarity
+                    }
+                    _ => f32::MIN,
+                };
+                best[y + 1][x + 1] = diagonal.max(best[y][x + 1]).max(best[y + 1][x]);
+            }
+        }
+
+        let mut seeds = vec![];
+        let (mut y, mut x) = (a.len(), b.len());
+        while y > 0 && x > 0 {
+            if best[y][x] == best[y - 1][x] {
+                y -= 1;
+            } else if best[y][x] == best[y][x - 1] {
+                x -= 1;
+            } else {
+                y -= 1;
+                x -= 1;
+                seeds.push(Coordinates(X(x), Y(y)));
+            }
+        }
+
+        seeds.reverse();
+        seeds
+    }
+
+    fn ngrams(&self, words: &[Word]) -> HashSet<String> {
+        let characters = words
+            .iter()
+            .map(|word| (self.characters)(word))
+            .collect::<Vec<_>>()
+            .join(" ")
+            .chars()
+            .collect::<Vec<_>>();
+
+        characters
+            .windows(self.n.max(1))
+            .map(|ngram| ngram.iter().collect())
+            .collect()
+    }
+}
+
+fn dice(a: &HashSet<String>, b: &HashSet<String>) -> f32 {
+    if a.is_empty() && b.is_empty() {
+        0.0
+    } else {
+        (2 * a.intersection(b).count()) as f32 / (a.len() + b.len()) as f32
+    }
+}
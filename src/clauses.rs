@@ -0,0 +1,220 @@
+use std::hash::Hash;
+use std::ops::Range;
+
+use super::hash::HashMap;
+use super::{Output, Sentence};
+
+/// Returns the offsets of the words of a sentence starting a new clause
+pub type Boundaries<Word> = Box<dyn Fn(&[Word]) -> Vec<usize>>;
+
+/// Second stage splitting the sentences of each aligned pair into clauses and aligning the clauses by the
+/// translations induced from the alignment, for texts whose long sentences (e.g. of legal documents) need
+/// a finer granularity than whole sentences. The clauses of a pair are aligned one to one in order,
+/// maximizing the total similarity, and clauses without a counterpart are left unaligned
+pub struct Clauses<Word> {
+    /// Boundaries of the clauses of the sentences of text `a`
+    pub a_boundaries: Boundaries<Word>,
+    /// Boundaries of the clauses of the sentences of text `b`
+    pub b_boundaries: Boundaries<Word>,
+    /// Minimum number of words of a clause, below which it is joined to the previous clause.
+    /// Defaults to `3`
+    pub min_words: usize,
+    /// Similarity of two clauses required for them to be aligned. Defaults to `0.1`
+    pub min_similarity: f32,
+}
+
+impl<Word: AsRef<str>> Default for Clauses<Word> {
+    fn default() -> Self {
+        Self {
+            a_boundaries: Self::punctuation(),
+            b_boundaries: Self::punctuation(),
+            min_words: 3,
+            min_similarity: 0.1,
+        }
+    }
+}
+
+impl<Word: AsRef<str>> Clauses<Word> {
+    /// Returns boundaries following the commas, semicolons and colons among the words, for tokenizations
+    /// which keep punctuation
+    pub fn punctuation() -> Boundaries<Word> {
+        Box::new(|words| {
+            words
+                .iter()
+                .enumerate()
+                .filter(|(_, word)| {
+                    matches!(word.as_ref(), "," | ";" | ":" | "，" | "；" | "：" | "、")
+                })
+                .map(|(offset, _)| offset + 1)
+                .collect()
+        })
+    }
+
+    /// Returns boundaries following the punctuation and preceding the `conjunctions` (e.g. "and", "but",
+    /// "which"), compared case insensitively
+    pub fn conjunctions(conjunctions: &[&str]) -> Boundaries<Word>
+    where
+        Word: 'static,
+    {
+        let conjunctions = conjunctions
+            .iter()
+            .map(|conjunction| conjunction.to_lowercase())
+            .collect::<Vec<_>>();
+        let punctuation = Self::punctuation();
+        Box::new(move |words| {
+            let mut boundaries = punctuation(words);
+            boundaries.extend(
+                words
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, word)| conjunctions.contains(&word.as_ref().to_lowercase()))
+                    .map(|(offset, _)| offset),
+            );
+            boundaries.sort_unstable();
+            boundaries.dedup();
+            boundaries
+        })
+    }
+}
+
+impl<Word> Clauses<Word> {
+    /// Returns the ranges of the words of the clauses of `words` at the `boundaries`
+    fn split(&self, words: &[Word], boundaries: &Boundaries<Word>) -> Vec<Range<usize>> {
+        let mut clauses: Vec<Range<usize>> = vec![];
+        let mut start = 0;
+        let mut boundaries = boundaries(words);
+        boundaries.push(words.len());
+        for end in boundaries {
+            if end <= start || end > words.len() {
+                continue;
+            }
+            match clauses.last_mut() {
+                // Short clauses are joined to the previous one
+                Some(last) if end - start < self.min_words => last.end = end,
+                _ => clauses.push(start..end),
+            }
+            start = end;
+        }
+        // A short first clause is joined to the next one
+        if clauses.len() > 1 && clauses[0].len() < self.min_words {
+            let first = clauses.remove(0);
+            clauses[0].start = first.start;
+        }
+
+        clauses
+    }
+}
+
+/// A clause of a sentence of text `a` aligned with a clause of a sentence of text `b`
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlignedClause {
+    /// Index of the sentence in text `a`
+    pub a: usize,
+    /// Index of the sentence in text `b`
+    pub b: usize,
+    /// Range of the offsets of the words of the clause in its sentence of text `a`
+    pub a_words: Range<usize>,
+    /// Range of the offsets of the words of the clause in its sentence of text `b`
+    pub b_words: Range<usize>,
+    /// Similarity of the clauses, the mean over the words of both clauses of the probability of their
+    /// most probable translation in the other clause
+    pub similarity: f32,
+}
+
+impl<'a, T, U> Output<'a, T, U> {
+    /// Returns the clauses of the aligned pairs aligned with one another by the `clauses`, ordered as the
+    /// `pairs` and then by the clauses of text `a`. Always empty unless the texts were aligned with
+    /// `Config::translations` enabled
+    pub fn clauses<Word>(&self, clauses: &Clauses<Word>) -> Vec<AlignedClause>
+    where
+        Word: Eq + Hash,
+        T: Sentence<Word>,
+        U: Sentence<Word>,
+    {
+        let mut lexicon: HashMap<(&Word, &Word), f32> = HashMap::default();
+        for translation in self.translations() {
+            let probability = lexicon.entry((translation.a, translation.b)).or_default();
+            *probability = probability.max(translation.probability);
+        }
+        if lexicon.is_empty() {
+            return vec![];
+        }
+
+        let mut aligned = vec![];
+        for pair in self.pairs() {
+            let (a_words, b_words) = (self.a[pair.a].words(), self.b[pair.b].words());
+            let a_clauses = clauses.split(a_words, &clauses.a_boundaries);
+            let b_clauses = clauses.split(b_words, &clauses.b_boundaries);
+            let similarity = |a_clause: &Range<usize>, b_clause: &Range<usize>| {
+                let best = |a_word: &Word, b_word: &Word| {
+                    lexicon.get(&(a_word, b_word)).copied().unwrap_or_default()
+                };
+                let a_sum = a_words[a_clause.clone()]
+                    .iter()
+                    .map(|a_word| {
+                        b_words[b_clause.clone()]
+                            .iter()
+                            .map(|b_word| best(a_word, b_word))
+                            .fold(0.0, f32::max)
+                    })
+                    .sum::<f32>();
+                let b_sum = b_words[b_clause.clone()]
+                    .iter()
+                    .map(|b_word| {
+                        a_words[a_clause.clone()]
+                            .iter()
+                            .map(|a_word| best(a_word, b_word))
+                            .fold(0.0, f32::max)
+                    })
+                    .sum::<f32>();
+                (a_sum + b_sum) / (a_clause.len() + b_clause.len()).max(1) as f32
+            };
+
+            // Maximum similarity monotone alignment of the clauses exceeding the minimum similarity
+            let similarities = a_clauses
+                .iter()
+                .map(|a_clause| {
+                    b_clauses
+                        .iter()
+                        .map(|b_clause| similarity(a_clause, b_clause))
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>();
+            let mut best = vec![vec![0.0f32; b_clauses.len() + 1]; a_clauses.len() + 1];
+            for i in 0..a_clauses.len() {
+                for j in 0..b_clauses.len() {
+                    let diagonal = match similarities[i][j] {
+                        similarity if similarity >= clauses.min_similarity => {
+                            best[i][j] + similarity
+                        }
+                        _ => f32::MIN,
+                    };
+                    best[i + 1][j + 1] = diagonal.max(best[i][j + 1]).max(best[i + 1][j]);
+                }
+            }
+
+            let mut pairs = vec![];
+            let (mut i, mut j) = (a_clauses.len(), b_clauses.len());
+            while i > 0 && j > 0 {
+                if best[i][j] == best[i - 1][j] {
+                    i -= 1;
+                } else if best[i][j] == best[i][j - 1] {
+                    j -= 1;
+                } else {
+                    i -= 1;
+                    j -= 1;
+                    pairs.push(AlignedClause {
+                        a: pair.a,
+                        b: pair.b,
+                        a_words: a_clauses[i].clone(),
+                        b_words: b_clauses[j].clone(),
+                        similarity: similarities[i][j],
+                    });
+                }
+            }
+            aligned.extend(pairs.into_iter().rev());
+        }
+
+        aligned
+    }
+}
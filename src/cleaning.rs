@@ -0,0 +1,170 @@
+use std::hash::Hash;
+
+use super::hash::HashSet;
+use super::lexicon::{support, Lexicon};
+use super::{Coordinates, Output, Sentence, SentenceAlignmentTable, WordSentenceIndex, X, Y};
+
+/// Assessment of the aligned pairs performed once the cycles have finished, flagging the pairs which are
+/// likely misaligned or unusable as bitext, as when filtering a corpus before training on it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cleaning {
+    /// Ratio of the length in words of the longer sentence of a pair to that of the shorter beyond which the
+    /// pair is flagged. Defaults to `3.0`
+    pub max_length_ratio: f32,
+    /// Fraction of the words of the shorter sentence of a pair which must have a counterpart in the other
+    /// sentence, among the word associations accepted while aligning, for the pair not to be flagged.
+    /// Defaults to `0.1`
+    pub min_overlap: f32,
+    /// Fraction of the distinct words of the shorter sentence of a pair found verbatim in the other sentence
+    /// beyond which the pair is flagged as untranslated. Defaults to `0.8`
+    pub max_copy: f32,
+    /// Removes the unlocked flagged pairs from the alignment, rather than only reporting them through
+    /// `Output::quality`. Defaults to `false`
+    pub remove: bool,
+}
+
+impl Default for Cleaning {
+    fn default() -> Self {
+        Self {
+            max_length_ratio: 3.0,
+            min_overlap: 0.1,
+            max_copy: 0.8,
+            remove: false,
+        }
+    }
+}
+
+/// A reason for flagging an aligned pair
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Issue {
+    /// The lengths of the sentences differ by more than the `max_length_ratio`
+    LengthRatio,
+    /// Too few of the words of the sentences are associated with one another
+    LowOverlap,
+    /// One sentence is mostly a copy of the other
+    Copy,
+    /// One of the sentences is empty or mostly a single repeated word
+    Garbage,
+}
+
+/// The assessment of an aligned pair
+#[derive(Debug, Clone, PartialEq)]
+pub struct PairQuality {
+    /// Index of the sentence in text `a`
+    pub a: usize,
+    /// Index of the sentence in text `b`
+    pub b: usize,
+    /// Ratio of the length in words of the longer sentence to that of the shorter
+    pub length_ratio: f32,
+    /// Fraction of the words of the shorter sentence with a counterpart in the other sentence
+    pub overlap: f32,
+    /// Fraction of the distinct words of the shorter sentence found verbatim in the other sentence
+    pub copy: f32,
+    /// Reasons for flagging the pair, empty unless it is likely misaligned
+    pub issues: Vec<Issue>,
+}
+
+impl PairQuality {
+    /// Returns true if any issue was found with the pair
+    pub fn is_flagged(&self) -> bool {
+        !self.issues.is_empty()
+    }
+}
+
+impl Cleaning {
+    /// Returns the assessment of each anchor of the SAT ordered by `a`, removing the unlocked flagged ones
+    /// if configured to
+    pub(crate) fn clean<'a, Word, T, U>(
+        &self,
+        sat: &mut SentenceAlignmentTable,
+        a: &'a [T],
+        b: &'a [U],
+        a_word_sentence_index: &WordSentenceIndex<'a, Word, Y>,
+        b_word_sentence_index: &WordSentenceIndex<'a, Word, X>,
+        lexicon: &Lexicon<'a, Word>,
+    ) -> Vec<PairQuality>
+    where
+        Word: Eq + Hash,
+        T: Sentence<Word>,
+        U: Sentence<Word>,
+    {
+        let mut anchors = sat.anchors().collect::<Vec<_>>();
+        anchors.sort_unstable_by_key(|&Coordinates(x, y)| (y, x));
+
+        let quality = anchors
+            .into_iter()
+            .map(|Coordinates(X(x), Y(y))| {
+                let (a_words, b_words) = (a[y].words(), b[x].words());
+                let shorter = a_words.len().min(b_words.len());
+                let length_ratio = a_words.len().max(b_words.len()) as f32 / shorter.max(1) as f32;
+
+                let overlap = support(
+                    lexicon,
+                    &a_words
+                        .iter()
+                        .filter_map(|word| a_word_sentence_index.indexed(word))
+                        .collect(),
+                    &b_words
+                        .iter()
+                        .filter_map(|word| b_word_sentence_index.indexed(word))
+                        .collect(),
+                );
+
+                let a_distinct = a_words.iter().collect::<HashSet<_>>();
+                let b_distinct = b_words.iter().collect::<HashSet<_>>();
+                let copy = a_distinct.intersection(&b_distinct).count() as f32
+                    / a_distinct.len().min(b_distinct.len()).max(1) as f32;
+
+                let mut issues = vec![];
+                if length_ratio > self.max_length_ratio {
+                    issues.push(Issue::LengthRatio);
+                }
+                if overlap < self.min_overlap {
+                    issues.push(Issue::LowOverlap);
+                }
+                if copy > self.max_copy {
+                    issues.push(Issue::Copy);
+                }
+                if is_garbage(a_words.len(), a_distinct.len())
+                    || is_garbage(b_words.len(), b_distinct.len())
+                {
+                    issues.push(Issue::Garbage);
+                }
+
+                PairQuality {
+                    a: y,
+                    b: x,
+                    length_ratio,
+                    overlap,
+                    copy,
+                    issues,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        if self.remove {
+            for pair in quality.iter().filter(|pair| pair.is_flagged()) {
+                let coordinates = Coordinates(X(pair.b), Y(pair.a));
+                if !sat.is_locked(coordinates) {
+                    sat.remove(coordinates);
+                }
+            }
+        }
+
+        quality
+    }
+}
+
+/// A sentence is garbage if it is empty, or long enough to judge and made of fewer distinct words than half
+/// its length
+fn is_garbage(len: usize, distinct: usize) -> bool {
+    len == 0 || (len >= 4 && distinct * 2 < len)
+}
+
+impl<'a, T, U> Output<'a, T, U> {
+    /// Returns the assessment of each pair aligned before cleaning, ordered by `a`, including the flagged
+    /// pairs which were removed. Always empty unless the texts were aligned with `Cleaning`
+    pub fn quality(&self) -> &[PairQuality] {
+        &self.quality
+    }
+}
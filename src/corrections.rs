@@ -0,0 +1,49 @@
+use super::{Coordinates, SentenceAlignmentTable, X, Y};
+
+/// Human decisions on pairs of sentence indices `(a, b)`, used to constrain `Config::realign`. Pairs
+/// outside the texts are ignored
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Corrections {
+    /// Pairs which must be aligned, locked as anchors. Evidence crossing these pairs is discarded
+    pub confirmed: Vec<(usize, usize)>,
+    /// Pairs which must not be aligned
+    pub rejected: Vec<(usize, usize)>,
+}
+
+impl Corrections {
+    /// Applies the corrections to the `sat` of texts of `a_len` and `b_len` sentences
+    pub(crate) fn apply(&self, sat: &mut SentenceAlignmentTable, a_len: usize, b_len: usize) {
+        let in_texts = |&&(a, b): &&(usize, usize)| a < a_len && b < b_len;
+        for &(a, b) in self.rejected.iter().filter(in_texts) {
+            sat.forbid(Coordinates(X(b), Y(a)));
+        }
+
+        for &(a, b) in self.confirmed.iter().filter(in_texts) {
+            sat.lock(Coordinates(X(b), Y(a)));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Config;
+
+    #[test]
+    fn corrections_outside_the_texts_are_ignored() {
+        let words = ["alpha", "beta", "gamma", "delta", "epsilon"];
+        let text = (0..20)
+            .map(|i| vec![words[i % words.len()], words[(i + 1) % words.len()]])
+            .collect::<Vec<_>>();
+        let text = text.iter().map(Vec::as_slice).collect::<Vec<_>>();
+        let previous = Config::default().align(&text, &text);
+        let corrections = Corrections {
+            confirmed: vec![(50, 50), (3, 3)],
+            rejected: vec![(20, 0)],
+        };
+
+        let output = Config::default().realign(&previous, &corrections);
+        assert!(output.pairs().any(|pair| pair.indices() == (3, 3)));
+        assert!(output.pairs().all(|pair| pair.a < 20 && pair.b < 20));
+    }
+}
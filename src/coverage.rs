@@ -0,0 +1,44 @@
+use super::Sentence;
+
+/// Measure of the weight of a sentence from its words
+pub type SentenceWeight<Word> = Box<dyn Fn(&[Word]) -> usize>;
+
+/// Weighting of the sentences counted towards the coverage of an alignment
+#[derive(Default)]
+pub enum CoverageWeighting<Word> {
+    /// Every sentence counts equally
+    #[default]
+    Sentences,
+    /// Sentences are weighted by their number of words, excluding the words filtered from the index
+    Words,
+    /// Sentences are weighted by the given measure of their words, e.g. `CoverageWeighting::characters`
+    Custom(SentenceWeight<Word>),
+}
+
+impl<Word: AsRef<str>> CoverageWeighting<Word> {
+    /// Weights sentences by the number of characters of their words
+    pub fn characters() -> Self {
+        Self::Custom(Box::new(|words| {
+            words.iter().map(|word| word.as_ref().chars().count()).sum()
+        }))
+    }
+}
+
+impl<Word: PartialEq> CoverageWeighting<Word> {
+    /// Returns the weight of each of the `sentences`, given the number of words of each which were indexed
+    pub(crate) fn weights<S: Sentence<Word>>(
+        &self,
+        sentences: &[S],
+        indexed_lengths: &[usize],
+    ) -> Vec<usize> {
+        sentences
+            .iter()
+            .zip(indexed_lengths)
+            .map(|(sentence, &len)| match self {
+                Self::Sentences => 1,
+                Self::Words => len,
+                Self::Custom(measure) => measure(sentence.words()),
+            })
+            .collect()
+    }
+}
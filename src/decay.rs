@@ -0,0 +1,55 @@
+use std::collections::HashMap;
+
+use super::sentence_alignment_table::{Score, SentenceAlignmentTable};
+use super::Coordinates;
+
+/// Decay of the scores of cells below the anchor threshold which are no longer reinforced, so that
+/// evidence gathered under the strict thresholds of early cycles does not linger until looser thresholds
+/// complete it into a false anchor
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Decay {
+    /// Number of cycles without reinforcement after which the score of a cell starts to decay.
+    /// Defaults to `3`
+    pub after: usize,
+    /// Amount by which a decaying score is decreased at the end of each cycle. Defaults to `1`
+    pub rate: usize,
+}
+
+impl Default for Decay {
+    fn default() -> Self {
+        Self { after: 3, rate: 1 }
+    }
+}
+
+/// Score of each cell below the anchor threshold at the end of the last cycle, and the last cycle in
+/// which it increased
+pub(crate) type Ages = HashMap<Coordinates, (Score, usize)>;
+
+impl Decay {
+    /// Decays the scores which have not increased since `after` cycles before `cycle`
+    pub(crate) fn apply(&self, sat: &mut SentenceAlignmentTable, ages: &mut Ages, cycle: usize) {
+        let threshold = sat.anchor_threshold();
+        let cells = sat
+            .cells()
+            .filter(|&(_, score)| score < threshold)
+            .collect::<Vec<_>>();
+
+        let mut next = Ages::with_capacity(cells.len());
+        for (coordinates, score) in cells {
+            let reinforced = match ages.get(&coordinates) {
+                Some(&(previous, reinforced)) if score <= previous => reinforced,
+                _ => cycle,
+            };
+            if cycle - reinforced >= self.after {
+                sat.subtract(coordinates, Score::from(self.rate));
+            }
+
+            let score = sat.score(coordinates);
+            if score > Score::ZERO {
+                next.insert(coordinates, (score, reinforced));
+            }
+        }
+
+        *ages = next;
+    }
+}
@@ -0,0 +1,140 @@
+use super::{Config, Lang, Normalization, Output, Sentence};
+
+/// Number of characters of each text sampled for language detection
+const SAMPLE_LENGTH: usize = 10_000;
+
+/// Languages detected in a pair of texts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Detection {
+    /// Language detected in text `a`, if it has bundled stop words
+    pub a: Option<Lang>,
+    /// Language detected in text `b`, if it has bundled stop words
+    pub b: Option<Lang>,
+    /// Both texts were detected as the same language. This is usually a data error when aligning
+    /// translations, e.g. a crawl which fetched the same edition twice
+    pub same_language: bool,
+}
+
+impl Detection {
+    /// Detects the languages of texts `a` and `b` from a sample of their first sentences
+    pub fn detect<Word, T, U>(a: &[T], b: &[U]) -> Self
+    where
+        Word: PartialEq + AsRef<str>,
+        T: Sentence<Word>,
+        U: Sentence<Word>,
+    {
+        let a_detected = whatlang::detect_lang(&sample(a));
+        let b_detected = whatlang::detect_lang(&sample(b));
+
+        Self {
+            a: a_detected.and_then(lang),
+            b: b_detected.and_then(lang),
+            same_language: a_detected.is_some() && a_detected == b_detected,
+        }
+    }
+
+    /// Returns the configuration for the detected languages, see `Config::with_languages`. Words are only
+    /// lowercased if the language of either text is unknown
+    pub fn config<Word: AsRef<str>>(&self) -> Config<Word> {
+        match (self.a, self.b) {
+            (Some(a), Some(b)) => Config::with_languages(a, b),
+            _ => Config {
+                normalizer: Some(
+                    Normalization {
+                        lowercase: true,
+                        ..Default::default()
+                    }
+                    .normalizer(),
+                ),
+                ..Default::default()
+            },
+        }
+    }
+}
+
+/// An aligned pair a sentence of which is reliably detected in a language other than that of its text
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LanguageMismatch {
+    /// Index of the sentence in text `a`
+    pub a: usize,
+    /// Index of the sentence in text `b`
+    pub b: usize,
+    /// Either sentence is in the language of the other text, i.e. the "translation" is untranslated source
+    /// text, as is pervasive in crawled corpora
+    pub untranslated: bool,
+}
+
+impl<'a, T, U> Output<'a, T, U> {
+    /// Returns the aligned pairs, ordered by `a`, either sentence of which is reliably detected in a language
+    /// other than the one detected for its text as a whole. Sentences too short to be detected reliably
+    /// are assumed to be in the expected language
+    pub fn language_mismatches<Word>(&self) -> Vec<LanguageMismatch>
+    where
+        Word: PartialEq + AsRef<str>,
+        T: Sentence<Word>,
+        U: Sentence<Word>,
+    {
+        let (Some(a_lang), Some(b_lang)) = (
+            whatlang::detect_lang(&sample(&self.a)),
+            whatlang::detect_lang(&sample(&self.b)),
+        ) else {
+            return vec![];
+        };
+
+        self.pairs()
+            .filter_map(|pair| {
+                let a_detected = detect_reliably(&self.a[pair.a]).unwrap_or(a_lang);
+                let b_detected = detect_reliably(&self.b[pair.b]).unwrap_or(b_lang);
+
+                (a_detected != a_lang || b_detected != b_lang).then_some(LanguageMismatch {
+                    a: pair.a,
+                    b: pair.b,
+                    untranslated: a_lang != b_lang
+                        && (a_detected == b_lang || b_detected == a_lang),
+                })
+            })
+            .collect()
+    }
+}
+
+/// Returns the language of `sentence`, if it is detected reliably
+fn detect_reliably<Word, S>(sentence: &S) -> Option<whatlang::Lang>
+where
+    Word: PartialEq + AsRef<str>,
+    S: Sentence<Word>,
+{
+    whatlang::detect(&sample(std::slice::from_ref(sentence)))
+        .filter(|info| info.is_reliable())
+        .map(|info| info.lang())
+}
+
+fn sample<Word, S>(text: &[S]) -> String
+where
+    Word: PartialEq + AsRef<str>,
+    S: Sentence<Word>,
+{
+    let mut sample = String::new();
+    for word in text.iter().flat_map(|sentence| sentence.words()) {
+        if sample.len() >= SAMPLE_LENGTH {
+            break;
+        }
+        sample.push_str(word.as_ref());
+        sample.push(' ');
+    }
+
+    sample
+}
+
+fn lang(lang: whatlang::Lang) -> Option<Lang> {
+    match lang {
+        whatlang::Lang::Nld => Some(Lang::Dutch),
+        whatlang::Lang::Eng => Some(Lang::English),
+        whatlang::Lang::Fra => Some(Lang::French),
+        whatlang::Lang::Deu => Some(Lang::German),
+        whatlang::Lang::Ita => Some(Lang::Italian),
+        whatlang::Lang::Por => Some(Lang::Portuguese),
+        whatlang::Lang::Rus => Some(Lang::Russian),
+        whatlang::Lang::Spa => Some(Lang::Spanish),
+        _ => None,
+    }
+}
@@ -0,0 +1,48 @@
+use std::hash::Hash;
+
+use super::sentence_alignment_table::Score;
+use super::{Config, Output, Sentence};
+
+impl<Word> Config<Word> {
+    /// Aligns the texts `a` and `b` of `previous` after sentences were appended to them, e.g. the new
+    /// installments of a serialized publication and of its ongoing translation. The anchors of
+    /// `previous` up to the last anchor of its chain are kept, so that the corridors through them are
+    /// narrow and the evidence gathered is mostly that of the sentences following it, while the word
+    /// frequencies remain those of the whole texts. The texts must begin with the sentences of
+    /// `previous`. Evidence of `previous` below the anchor threshold is discarded
+    pub fn align_appended<'a, T, U>(
+        self,
+        previous: &Output<'_, T, U>,
+        a: &'a [T],
+        b: &'a [U],
+    ) -> Output<'a, T, U>
+    where
+        Word: Eq + Hash + 'a,
+        T: Sentence<Word>,
+        U: Sentence<Word>,
+    {
+        let Some(last) = previous
+            .sat
+            .chain()
+            .into_iter()
+            .rev()
+            .find(|&coordinates| previous.sat.is_anchor(coordinates))
+            .filter(|last| last.y().0 < a.len() && last.x().0 < b.len())
+        else {
+            return self.align(a, b);
+        };
+
+        let mut sat = previous.sat.clone();
+        sat.set_anchor_threshold(Score::from(self.anchor_threshold));
+        sat.raise_locked();
+        sat.retain_anchors();
+        for anchor in sat.anchors().collect::<Vec<_>>() {
+            if anchor.x() > last.x() || anchor.y() > last.y() {
+                sat.remove(anchor);
+            }
+        }
+        sat.resize(a.len(), b.len());
+
+        self.run(a, b, Some(sat))
+    }
+}
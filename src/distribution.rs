@@ -0,0 +1,88 @@
+use std::ops::RangeInclusive;
+
+use super::Output;
+
+/// Number of scored pairs whose score lies within a range
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bin {
+    /// Range of the scores counted by the bin
+    pub scores: RangeInclusive<usize>,
+    /// Number of pairs whose score lies within `scores`
+    pub count: usize,
+}
+
+/// Summary statistics of the scores of all pairs with a non-zero score
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct ScoreSummary {
+    /// Number of pairs with a non-zero score
+    pub count: usize,
+    pub mean: f32,
+    pub median: f32,
+    pub max: usize,
+    /// Fraction of the pairs scored at or above the anchor threshold
+    pub above_threshold: f32,
+}
+
+impl<'a, T, U> Output<'a, T, U> {
+    /// Returns a histogram of the scores of all pairs with a non-zero score, dividing the scores from `1` to
+    /// the maximum score into at most `bins` bins of equal width
+    pub fn score_histogram(&self, bins: usize) -> Vec<Bin> {
+        let scores = self.scores();
+        let Some(&max) = scores.last() else {
+            return vec![];
+        };
+        if bins == 0 {
+            return vec![];
+        }
+
+        let width = max.div_ceil(bins);
+        let mut histogram = (1..=max)
+            .step_by(width)
+            .map(|start| Bin {
+                scores: start..=(start + width - 1).min(max),
+                count: 0,
+            })
+            .collect::<Vec<_>>();
+        for score in scores {
+            histogram[(score - 1) / width].count += 1;
+        }
+
+        histogram
+    }
+
+    /// Returns summary statistics of the scores of all pairs with a non-zero score
+    pub fn score_summary(&self) -> ScoreSummary {
+        let scores = self.scores();
+        let Some(&max) = scores.last() else {
+            return ScoreSummary::default();
+        };
+
+        let count = scores.len();
+        let median = if count.is_multiple_of(2) {
+            (scores[count / 2 - 1] + scores[count / 2]) as f32 / 2.0
+        } else {
+            scores[count / 2] as f32
+        };
+        let threshold = self.sat.anchor_threshold().0;
+
+        ScoreSummary {
+            count,
+            mean: scores.iter().sum::<usize>() as f32 / count as f32,
+            median,
+            max,
+            above_threshold: scores.iter().filter(|&&score| score >= threshold).count() as f32
+                / count as f32,
+        }
+    }
+
+    /// Returns the non-zero scores of the SAT in ascending order
+    fn scores(&self) -> Vec<usize> {
+        let mut scores = self
+            .sat
+            .cells()
+            .map(|(_, score)| score.0)
+            .collect::<Vec<_>>();
+        scores.sort_unstable();
+        scores
+    }
+}
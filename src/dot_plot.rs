@@ -0,0 +1,37 @@
+use super::{Coordinates, Output, X, Y};
+
+impl<'a, T, U> Output<'a, T, U> {
+    /// Returns a plot of the alignment for display in a terminal `width` characters wide, with text `b`
+    /// across and text `a` down. Each character covers a block of sentences, drawn as `#` if it contains an
+    /// anchor, `.` if it only contains pairs scored below the anchor threshold and blank otherwise. The
+    /// number of lines keeps the proportions of the texts for characters about twice as tall as wide
+    pub fn dot_plot(&self, width: usize) -> String {
+        let (a_len, b_len) = (self.a.len().max(1), self.b.len().max(1));
+        // Less the borders
+        let columns = width.saturating_sub(2).clamp(1, b_len);
+        let rows = (a_len * columns).div_ceil(b_len * 2).clamp(1, a_len);
+
+        let mut plot = vec![vec![' '; columns]; rows];
+        for (coordinates @ Coordinates(X(x), Y(y)), _) in self.sat.cells() {
+            let cell = &mut plot[y * rows / a_len][x * columns / b_len];
+            if self.sat.is_anchor(coordinates) {
+                *cell = '#';
+            } else if *cell == ' ' {
+                *cell = '.';
+            }
+        }
+
+        let mut rendered = format!(
+            "a: {} sentences down, b: {} across\n",
+            self.a.len(),
+            self.b.len()
+        );
+        for row in plot {
+            rendered.push('|');
+            rendered.extend(row);
+            rendered.push_str("|\n");
+        }
+
+        rendered
+    }
+}
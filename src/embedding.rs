@@ -0,0 +1,126 @@
+use std::collections::HashMap;
+
+use super::{AlignableSentenceTable, Coordinates, Sentence, X, Y};
+
+/// Provider of sentence embeddings, e.g. from a multilingual sentence encoder
+pub trait EmbeddingProvider<Word> {
+    /// Returns the embedding of the sentence consisting of `words`. All embeddings must have the same
+    /// dimensions
+    fn embed(&self, words: &[Word]) -> Vec<f32>;
+}
+
+impl<Word, F> EmbeddingProvider<Word> for F
+where
+    F: Fn(&[Word]) -> Vec<f32>,
+{
+    fn embed(&self, words: &[Word]) -> Vec<f32> {
+        self(words)
+    }
+}
+
+/// Scores the cells of the AST by cosine similarity of sentence embeddings. Each cycle, cells whose
+/// sentences are each other's most similar counterpart within the corridor contribute evidence to the
+/// SAT alongside the word associations
+pub struct Embeddings<Word> {
+    pub provider: Box<dyn EmbeddingProvider<Word>>,
+    /// Cosine similarity required for a cell to contribute evidence. Defaults to `0.7`
+    pub similarity_threshold: f32,
+    /// Score contributed to a cell by its embeddings, once per run. Defaults to `1`
+    pub weight: usize,
+}
+
+impl<Word> Embeddings<Word> {
+    pub fn new(provider: impl EmbeddingProvider<Word> + 'static) -> Self {
+        Self {
+            provider: Box::new(provider),
+            similarity_threshold: 0.7,
+            weight: 1,
+        }
+    }
+
+    /// Returns the unit-length embeddings of the sentences of texts `a` and `b`. Embeddings whose
+    /// dimensions differ from those of the first embedding are left empty, so that their sentences
+    /// contribute no evidence
+    pub(crate) fn embed<T, U>(&self, a: &[T], b: &[U]) -> (Vec<Vec<f32>>, Vec<Vec<f32>>)
+    where
+        Word: PartialEq,
+        T: Sentence<Word>,
+        U: Sentence<Word>,
+    {
+        let mut dimensions = None;
+        let mut embed = |words: &[Word]| {
+            let mut embedding = self.provider.embed(words);
+            if *dimensions.get_or_insert(embedding.len()) != embedding.len() {
+                return vec![];
+            }
+            let norm = embedding.iter().map(|v| v * v).sum::<f32>().sqrt();
+            if norm > 0.0 {
+                embedding.iter_mut().for_each(|v| *v /= norm);
+            }
+            embedding
+        };
+        let a = a.iter().map(|sentence| embed(sentence.words())).collect();
+        let b = b.iter().map(|sentence| embed(sentence.words())).collect();
+
+        (a, b)
+    }
+
+    /// Returns the cells of the AST whose sentences are mutually most similar above the threshold
+    pub(crate) fn candidates(
+        &self,
+        ast: &AlignableSentenceTable,
+        a: &[Vec<f32>],
+        b: &[Vec<f32>],
+    ) -> Vec<Coordinates> {
+        let mut a_best: HashMap<Y, (X, f32)> = HashMap::new();
+        let mut b_best: HashMap<X, (Y, f32)> = HashMap::new();
+
+        for Coordinates(x, y) in ast.all() {
+            if a[y.0].is_empty() || b[x.0].is_empty() {
+                continue;
+            }
+            let similarity = a[y.0].iter().zip(&b[x.0]).map(|(a, b)| a * b).sum::<f32>();
+            if similarity < self.similarity_threshold {
+                continue;
+            }
+
+            let best = a_best.entry(y).or_insert((x, similarity));
+            if similarity > best.1 {
+                *best = (x, similarity);
+            }
+            let best = b_best.entry(x).or_insert((y, similarity));
+            if similarity > best.1 {
+                *best = (y, similarity);
+            }
+        }
+
+        let mut candidates = a_best
+            .into_iter()
+            .filter(|(y, (x, _))| b_best.get(x).is_some_and(|(best, _)| best == y))
+            .map(|(y, (x, _))| Coordinates(x, y))
+            .collect::<Vec<_>>();
+        candidates.sort_by_key(|Coordinates(x, y)| (*x, *y));
+
+        candidates
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mismatched_dimensions_are_left_empty() {
+        let embeddings = Embeddings::new(|words: &[&str]| vec![1.0; words.len()]);
+        let a = [vec!["one", "two"], vec!["three"]];
+        let b = [vec!["uno", "dos"], vec!["tres", "cuatro", "cinco"]];
+        let a = a.iter().map(Vec::as_slice).collect::<Vec<_>>();
+        let b = b.iter().map(Vec::as_slice).collect::<Vec<_>>();
+
+        let (a, b) = embeddings.embed(&a, &b);
+        assert_eq!(a[0].len(), 2);
+        assert!(a[1].is_empty());
+        assert_eq!(b[0].len(), 2);
+        assert!(b[1].is_empty());
+    }
+}
@@ -0,0 +1,129 @@
+use super::ConfusionCosts;
+
+/// Measure of the similarity in `[0, 1]` of a pair of words
+pub type Measure<Word> = Box<dyn for<'a> Fn(&'a Word, &'a Word) -> f32>;
+
+/// A signal of the similarity of a pair of words
+pub enum Signal<Word> {
+    /// Similarity of the distributions of the words over the sentences of the corridor, the default
+    /// measure of association
+    Cooccurrence,
+    /// Similarity of the mean relative positions of the words in their texts
+    Positional,
+    /// Similarity of the mean relative positions of the words within their sentences, for languages with
+    /// similar word order. Word positions are only recorded when an ensemble includes this signal
+    WordOrder,
+    /// Similarity given by an arbitrary measure, e.g. `Signal::orthographic` or `Signal::dictionary`
+    Measure(Measure<Word>),
+}
+
+impl<Word: AsRef<str>> Signal<Word> {
+    /// Similarity of the spelling of the words, one minus their edit distance relative to the longer word.
+    /// Useful for cognates and names shared by related languages
+    pub fn orthographic() -> Self {
+        Self::orthographic_with(ConfusionCosts::default())
+    }
+
+    /// Similarity of the spelling of the words as `Signal::orthographic`, with the substitutions of
+    /// characters weighted by the `confusion_costs`, e.g. those of the OCR engine which produced the texts
+    pub fn orthographic_with(confusion_costs: ConfusionCosts) -> Self {
+        Self::Measure(Box::new(move |a, b| {
+            spelling_similarity(
+                &a.as_ref().chars().collect::<Vec<_>>(),
+                &b.as_ref().chars().collect::<Vec<_>>(),
+                &confusion_costs,
+            )
+        }))
+    }
+
+    /// Similarity of the spelling of the words once transliterated to lowercase Latin letters, as
+    /// `Signal::orthographic`. Useful for names written in different scripts, e.g. "Пушкин" and "Pushkin"
+    #[cfg(feature = "transliteration")]
+    pub fn transliterated() -> Self {
+        Self::Measure(Box::new(|a, b| {
+            spelling_similarity(
+                &transliterate(a.as_ref()).chars().collect::<Vec<_>>(),
+                &transliterate(b.as_ref()).chars().collect::<Vec<_>>(),
+                &ConfusionCosts::default(),
+            )
+        }))
+    }
+}
+
+/// Returns one minus the edit distance of `a` and `b` relative to the longer
+fn spelling_similarity(a: &[char], b: &[char], confusion_costs: &ConfusionCosts) -> f32 {
+    let len = a.len().max(b.len());
+    if len == 0 {
+        0.0
+    } else {
+        1.0 - confusion_costs.distance(a, b, len as f32) / len as f32
+    }
+}
+
+/// Returns `word` transliterated to lowercase ASCII
+#[cfg(feature = "transliteration")]
+pub(crate) fn transliterate(word: &str) -> String {
+    deunicode::deunicode(word).to_lowercase()
+}
+
+impl<Word> Signal<Word> {
+    /// Similarity of `1` for the pairs of words found in a bilingual dictionary, and `0` otherwise
+    pub fn dictionary(dictionary: impl Fn(&Word, &Word) -> bool + 'static) -> Self {
+        Self::Measure(Box::new(move |a, b| f32::from(u8::from(dictionary(a, b)))))
+    }
+}
+
+/// Weighted combination of similarity signals forming the similarity of word associations
+pub struct Ensemble<Word> {
+    /// Signals with their weights. The similarity of an association is the weighted mean of its signals.
+    /// Defaults to `Signal::Cooccurrence` alone
+    pub signals: Vec<(Signal<Word>, f32)>,
+}
+
+impl<Word> Default for Ensemble<Word> {
+    fn default() -> Self {
+        Self {
+            signals: vec![(Signal::Cooccurrence, 1.0)],
+        }
+    }
+}
+
+impl<Word> Ensemble<Word> {
+    /// Returns true if the ensemble includes `Signal::WordOrder`
+    pub(crate) fn uses_word_order(&self) -> bool {
+        self.signals
+            .iter()
+            .any(|(signal, _)| matches!(signal, Signal::WordOrder))
+    }
+
+    /// Returns the weighted mean of the signals for the words `a` and `b`. The co-occurrence and positional
+    /// similarities are only computed if the ensemble includes them
+    pub(crate) fn similarity(
+        &self,
+        a: &Word,
+        b: &Word,
+        cooccurrence: impl Fn() -> f32,
+        positional: impl Fn() -> f32,
+        word_order: impl Fn() -> f32,
+    ) -> f32 {
+        let mut total = 0.0;
+        let mut weights = 0.0;
+
+        for (signal, weight) in &self.signals {
+            let similarity = match signal {
+                Signal::Cooccurrence => cooccurrence(),
+                Signal::Positional => positional(),
+                Signal::WordOrder => word_order(),
+                Signal::Measure(measure) => measure(a, b),
+            };
+            total += weight * similarity;
+            weights += weight;
+        }
+
+        if weights > 0.0 {
+            total / weights
+        } else {
+            0.0
+        }
+    }
+}
@@ -0,0 +1,67 @@
+use super::AlignedPair;
+
+/// Event emitted while an alignment is running
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event<'a, Word> {
+    /// A cycle is starting with the given thresholds
+    CycleStarted {
+        cycle: usize,
+        similarity_threshold: f32,
+        frequency_threshold: usize,
+    },
+    /// An association contributed evidence to the given pairs of sentence indices `(a, b)`
+    AssociationAccepted {
+        a_word: &'a Word,
+        b_word: &'a Word,
+        similarity: f32,
+        pairs: Vec<(usize, usize)>,
+    },
+    /// An association was discarded for the given reason. Only associations proposing a pair which would
+    /// cross an existing alignment are reported, unless `Config::rejections` is enabled
+    AssociationRejected {
+        a_word: &'a Word,
+        b_word: &'a Word,
+        similarity: f32,
+        a_occurrences: usize,
+        b_occurrences: usize,
+        reason: Rejection,
+    },
+    /// The pair of sentence indices `(a, b)` reached the anchor threshold
+    AnchorCommitted { a: usize, b: usize, score: usize },
+    /// A cycle finished with the given coverage
+    CycleFinished { cycle: usize, coverage: f32 },
+    /// The alignment finished, after the phases following the cycles, with the given anchors. Anchors may
+    /// have been removed since they were committed, during the cycles (e.g. by `Config::decay`) or after
+    /// them (e.g. by `Config::viterbi`)
+    Finished { anchors: Vec<AlignedPair> },
+}
+
+/// Reason for which an association was discarded
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Rejection {
+    /// The similarity of the association was below the threshold of the cycle
+    Similarity { threshold: f32 },
+    /// One of the words occurs fewer times than the threshold of the cycle
+    Frequency { threshold: usize },
+    /// The occurrences of one of the words exceed those of the other by more than the ratio
+    OccurrenceRatio { max_ratio: f32 },
+    /// The pair of sentence indices `(a, b)` proposed by the association would cross an existing alignment
+    Crossover { a: usize, b: usize },
+    /// The association proposed no pairs, as its candidates in the corridor were ambiguous (or forbidden,
+    /// or vetoed by the reranker)
+    Ambiguous,
+}
+
+/// Receives the events of an alignment as they happen
+pub trait Observer<Word> {
+    fn observe(&self, event: Event<'_, Word>);
+}
+
+impl<Word, F> Observer<Word> for F
+where
+    F: Fn(Event<'_, Word>),
+{
+    fn observe(&self, event: Event<'_, Word>) {
+        self(event)
+    }
+}
@@ -0,0 +1,117 @@
+use std::hash::Hash;
+
+use super::hash::HashMap;
+use super::{Coordinates, Normalizer, Sentence, X, Y};
+
+/// Pre-pass anchoring the sentences occurring identically in both texts before any word association,
+/// such as the shared headings of documents, untranslated quotations or the unchanged sentences of texts
+/// in the same language. Sentences are compared by their words, as normalized by the
+/// `Config::normalizer` if any, and only those occurring once in each text are matched. Matches crossing
+/// one another are dropped, keeping the longest sequence of matches in the order of both texts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExactMatches {
+    /// Minimum number of words of a sentence for it to be matched, as very short sentences (e.g. numbers
+    /// or interjections) match by chance. Defaults to `2`
+    pub min_words: usize,
+}
+
+impl Default for ExactMatches {
+    fn default() -> Self {
+        Self { min_words: 2 }
+    }
+}
+
+impl ExactMatches {
+    /// Returns the cells of the sentences of texts `a` and `b` matching exactly, in order
+    pub(crate) fn matches<Word, T, U>(
+        &self,
+        a: &[T],
+        b: &[U],
+        normalizer: Option<&Normalizer<Word>>,
+    ) -> Vec<Coordinates>
+    where
+        Word: Eq + Hash,
+        T: Sentence<Word>,
+        U: Sentence<Word>,
+    {
+        let eligible = |words: &[Word]| !words.is_empty() && words.len() >= self.min_words;
+        let pairs = match normalizer {
+            Some(normalizer) => {
+                let normalized = |words: &[Word]| {
+                    eligible(words).then(|| words.iter().map(normalizer).collect::<Vec<_>>())
+                };
+                unique_pairs(
+                    a.iter().map(|sentence| normalized(sentence.words())),
+                    b.iter().map(|sentence| normalized(sentence.words())),
+                )
+            }
+            None => unique_pairs(
+                a.iter()
+                    .map(|sentence| Some(sentence.words()).filter(|words| eligible(words))),
+                b.iter()
+                    .map(|sentence| Some(sentence.words()).filter(|words| eligible(words))),
+            ),
+        };
+
+        increasing(pairs)
+            .into_iter()
+            .map(|(y, x)| Coordinates(X(x), Y(y)))
+            .collect()
+    }
+}
+
+/// Returns the pairs of indices `(a, b)` of the keys occurring exactly once in each text, ordered by `a`
+fn unique_pairs<K: Eq + Hash>(
+    a: impl Iterator<Item = Option<K>>,
+    b: impl Iterator<Item = Option<K>>,
+) -> Vec<(usize, usize)> {
+    let a = unique(a);
+    let b = unique(b);
+
+    let mut pairs = a
+        .iter()
+        .filter_map(|(key, &i)| Some((i?, b.get(key).copied()??)))
+        .collect::<Vec<_>>();
+    pairs.sort_unstable();
+    pairs
+}
+
+/// Returns the index of each key occurring once among the `keys`, and `None` for the others
+fn unique<K: Eq + Hash>(keys: impl Iterator<Item = Option<K>>) -> HashMap<K, Option<usize>> {
+    let mut occurrences: HashMap<K, Option<usize>> = HashMap::default();
+    for (i, key) in keys.enumerate() {
+        if let Some(key) = key {
+            occurrences
+                .entry(key)
+                .and_modify(|unique| *unique = None)
+                .or_insert(Some(i));
+        }
+    }
+
+    occurrences
+}
+
+/// Returns the longest subsequence of the `pairs`, ordered by `a`, which is also increasing in `b`
+pub(crate) fn increasing(pairs: Vec<(usize, usize)>) -> Vec<(usize, usize)> {
+    // Index of the pair ending the shortest tail of each length, and the pair preceding each pair
+    let mut tails: Vec<usize> = vec![];
+    let mut previous = vec![None; pairs.len()];
+    for (i, &(_, b)) in pairs.iter().enumerate() {
+        let length = tails.partition_point(|&j| pairs[j].1 < b);
+        previous[i] = length.checked_sub(1).map(|length| tails[length]);
+        if length == tails.len() {
+            tails.push(i);
+        } else {
+            tails[length] = i;
+        }
+    }
+
+    let mut sequence = vec![];
+    let mut next = tails.last().copied();
+    while let Some(i) = next {
+        sequence.push(pairs[i]);
+        next = previous[i];
+    }
+    sequence.reverse();
+    sequence
+}
@@ -0,0 +1,59 @@
+use std::hash::Hash;
+
+use super::{WordSentenceIndex, X, Y};
+
+/// Derivation of the word frequency thresholds from the distribution of the occurrences of the words of
+/// the texts being aligned, rather than absolute counts. The thresholds are set so that a share of the
+/// vocabulary of the texts is eligible for the WAT, which a fixed count only achieves for texts of a
+/// particular size
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FrequencyScaling {
+    /// Share of the vocabulary of the texts, most frequent first, eligible in the first cycle.
+    /// Defaults to `0.05`
+    pub initial_share: f32,
+    /// Share of the vocabulary of the texts eligible once the threshold has been tapered.
+    /// Defaults to `0.5`
+    pub final_share: f32,
+    /// Number of cycles over which the threshold is tapered from the `initial_share` to the
+    /// `final_share`. Defaults to `5`
+    pub cycles: usize,
+}
+
+impl Default for FrequencyScaling {
+    fn default() -> Self {
+        Self {
+            initial_share: 0.05,
+            final_share: 0.5,
+            cycles: 5,
+        }
+    }
+}
+
+impl FrequencyScaling {
+    /// Returns the word frequency threshold, taper and minimum for the texts of the indices
+    pub(crate) fn thresholds<Word: Eq + Hash>(
+        &self,
+        a_word_sentence_index: &WordSentenceIndex<'_, Word, Y>,
+        b_word_sentence_index: &WordSentenceIndex<'_, Word, X>,
+    ) -> (usize, usize, usize) {
+        let mut counts = a_word_sentence_index
+            .occurrence_counts()
+            .chain(b_word_sentence_index.occurrence_counts())
+            .collect::<Vec<_>>();
+        counts.sort_unstable_by(|a, b| b.cmp(a));
+
+        let at_share = |share: f32| {
+            let rank = (share.clamp(0.0, 1.0) * counts.len() as f32).ceil() as usize;
+            counts
+                .get(rank.saturating_sub(1))
+                .copied()
+                .unwrap_or(1)
+                .max(1)
+        };
+        let minimum = at_share(self.final_share);
+        let threshold = at_share(self.initial_share).max(minimum);
+        let taper = (threshold - minimum).div_ceil(self.cycles.max(1));
+
+        (threshold, taper, minimum)
+    }
+}
@@ -0,0 +1,104 @@
+use std::ops::Range;
+
+use super::{AlignedSpan, Output, X, Y};
+
+/// One of the two aligned texts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Side {
+    A,
+    B,
+}
+
+/// A maximal span of consecutive sentences of one text without any counterpart in the other, such as an
+/// untranslated passage or a translator's insertion
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Gap {
+    /// Text containing the sentences without a counterpart
+    pub side: Side,
+    /// Range of the sentences without a counterpart
+    pub range: Range<usize>,
+    /// Range of the sentences of the other text between the anchors bounding the gap. An empty range
+    /// indicates the sentences were inserted, otherwise they may have been freely translated
+    pub opposite: Range<usize>,
+}
+
+impl Gap {
+    /// Returns the number of sentences without a counterpart
+    pub fn len(&self) -> usize {
+        self.range.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.range.is_empty()
+    }
+
+    /// Returns the span of the gap and the sentences of the other text facing it
+    pub fn span(&self) -> AlignedSpan {
+        let (a_range, b_range) = match self.side {
+            Side::A => (self.range.clone(), self.opposite.clone()),
+            Side::B => (self.opposite.clone(), self.range.clone()),
+        };
+        AlignedSpan {
+            a_range,
+            b_range,
+            confidence: 0.0,
+        }
+    }
+}
+
+impl<'a, T, U> Output<'a, T, U> {
+    /// Returns the gaps of both texts, those of text `a` followed by those of text `b`, in text order
+    pub fn gaps(&self) -> Vec<Gap> {
+        let a_partners = |i: usize| {
+            self.a_alignments
+                .get(&Y(i))
+                .map(|xs| xs.iter().map(|X(j)| *j))
+        };
+        let b_partners = |i: usize| {
+            self.b_alignment_map()
+                .get(&X(i))
+                .map(|ys| ys.iter().map(|Y(j)| *j))
+        };
+
+        let mut gaps = side_gaps(Side::A, self.a.len(), self.b.len(), a_partners);
+        gaps.extend(side_gaps(Side::B, self.b.len(), self.a.len(), b_partners));
+        gaps
+    }
+}
+
+fn side_gaps<I: DoubleEndedIterator<Item = usize>>(
+    side: Side,
+    len: usize,
+    opposite_len: usize,
+    partners: impl Fn(usize) -> Option<I>,
+) -> Vec<Gap> {
+    let mut gaps = vec![];
+    let mut start = None;
+
+    for i in 0..=len {
+        match (start, i < len && partners(i).is_none()) {
+            (None, true) => start = Some(i),
+            (Some(s), false) => {
+                let opposite_start = s
+                    .checked_sub(1)
+                    .and_then(&partners)
+                    .and_then(|mut partners| partners.next_back())
+                    .map_or(0, |j| j + 1);
+                let opposite_end = partners(i)
+                    .and_then(|mut partners| partners.next())
+                    .unwrap_or(opposite_len)
+                    .max(opposite_start);
+
+                gaps.push(Gap {
+                    side,
+                    range: s..i,
+                    opposite: opposite_start..opposite_end,
+                });
+                start = None;
+            }
+            _ => {}
+        }
+    }
+
+    gaps
+}
@@ -0,0 +1,8 @@
+//! Maps and sets of the hot paths of the algorithm, which use the faster Fx hasher rather than SipHash
+//! with the `fxhash` feature. Words are usually borrowed from the texts, so resistance to collisions
+//! engineered by an attacker is rarely needed
+
+#[cfg(feature = "fxhash")]
+pub(crate) use rustc_hash::{FxHashMap as HashMap, FxHashSet as HashSet};
+#[cfg(not(feature = "fxhash"))]
+pub(crate) use std::collections::{HashMap, HashSet};
@@ -0,0 +1,195 @@
+use std::fmt::{Display, Write as _};
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use super::ndjson::{number, string};
+use super::{Event, Observer};
+
+/// Observer serializing a compact snapshot of each cycle of an alignment, so that an external inspector
+/// may replay and debug the run without linking against the crate. Each snapshot is a JSON object
+///
+/// ```text
+/// {"cycle":0,"similarity_threshold":0.8,"frequency_threshold":10,"coverage":0.5,
+///  "anchors":[[a,b,score],...],"associations":[{"a":"word","b":"word","similarity":0.9,"pairs":[[a,b],...]},...]}
+/// ```
+///
+/// where `anchors` are the pairs of sentence indices committed as anchors during the cycle, in the order
+/// they were committed, with their scores, and `associations` the associations accepted during the cycle
+/// with the pairs they contributed evidence to. The similarity and coverage are `null` where not finite.
+/// Anchors may also be lowered or removed, within the cycles (e.g. by `Config::decay`) and after them
+/// (e.g. by `Config::viterbi`), so the run ends with a final snapshot (the file `final.json`)
+///
+/// ```text
+/// {"final":true,"anchors":[[a,b,score],...],"alignment":[[a,b,score],...]}
+/// ```
+///
+/// where `anchors` are the pairs committed after the cycles and `alignment` every anchor of the finished
+/// alignment. Errors writing are ignored
+pub struct SnapshotWriter<W> {
+    state: Mutex<State<W>>,
+}
+
+enum Sink<W> {
+    /// Snapshots written one per line
+    Stream(W),
+    /// Snapshots written each to its own file `cycle-NNNN.json` in the directory
+    Directory(PathBuf),
+}
+
+struct State<W> {
+    sink: Sink<W>,
+    thresholds: Option<(f32, usize)>,
+    anchors: Vec<String>,
+    associations: Vec<String>,
+}
+
+impl<W: Write> SnapshotWriter<W> {
+    /// Creates an observer writing the snapshots to `writer` as newline-delimited JSON, for use as the
+    /// `Config::observer`
+    pub fn new(writer: W) -> Self {
+        Self::with_sink(Sink::Stream(writer))
+    }
+
+    fn with_sink(sink: Sink<W>) -> Self {
+        Self {
+            state: Mutex::new(State {
+                sink,
+                thresholds: None,
+                anchors: vec![],
+                associations: vec![],
+            }),
+        }
+    }
+}
+
+impl<W: Write> State<W> {
+    /// Writes the `snapshot` to the stream, or to the file `name` of the directory
+    fn write(&mut self, name: &str, snapshot: String) {
+        let _ = match &mut self.sink {
+            Sink::Stream(writer) => writeln!(writer, "{snapshot}").and_then(|_| writer.flush()),
+            Sink::Directory(directory) => fs::write(directory.join(name), snapshot + "\n"),
+        };
+    }
+}
+
+impl SnapshotWriter<File> {
+    /// Creates an observer writing each snapshot to its own file `cycle-NNNN.json` in `directory`, which
+    /// is created if it does not exist
+    pub fn directory(directory: impl Into<PathBuf>) -> io::Result<Self> {
+        let directory = directory.into();
+        fs::create_dir_all(&directory)?;
+
+        Ok(Self::with_sink(Sink::Directory(directory)))
+    }
+}
+
+impl<W: Write, Word: Display> Observer<Word> for SnapshotWriter<W> {
+    fn observe(&self, event: Event<'_, Word>) {
+        let Ok(mut state) = self.state.lock() else {
+            return;
+        };
+
+        match event {
+            Event::CycleStarted {
+                similarity_threshold,
+                frequency_threshold,
+                ..
+            } => state.thresholds = Some((similarity_threshold, frequency_threshold)),
+            Event::AnchorCommitted { a, b, score } => {
+                state.anchors.push(format!("[{a},{b},{score}]"))
+            }
+            Event::AssociationAccepted {
+                a_word,
+                b_word,
+                similarity,
+                pairs,
+            } => {
+                let mut association = format!(
+                    r#"{{"a":{},"b":{},"similarity":{},"pairs":["#,
+                    string(&a_word.to_string()),
+                    string(&b_word.to_string()),
+                    number(similarity)
+                );
+                for (i, (a, b)) in pairs.into_iter().enumerate() {
+                    let separator = if i == 0 { "" } else { "," };
+                    let _ = write!(association, "{separator}[{a},{b}]");
+                }
+                association.push_str("]}");
+                state.associations.push(association);
+            }
+            Event::CycleFinished { cycle, coverage } => {
+                let (similarity_threshold, frequency_threshold) = state.thresholds.take().map_or(
+                    ("null".to_owned(), "null".to_owned()),
+                    |(similarity, frequency)| (number(similarity), frequency.to_string()),
+                );
+                let snapshot = format!(
+                    r#"{{"cycle":{cycle},"similarity_threshold":{similarity_threshold},"frequency_threshold":{frequency_threshold},"coverage":{},"anchors":[{}],"associations":[{}]}}"#,
+                    number(coverage),
+                    state.anchors.join(","),
+                    state.associations.join(",")
+                );
+                state.anchors.clear();
+                state.associations.clear();
+                state.write(&format!("cycle-{cycle:04}.json"), snapshot);
+            }
+            Event::Finished { anchors } => {
+                let alignment = anchors
+                    .iter()
+                    .map(|pair| format!("[{},{},{}]", pair.a, pair.b, pair.score))
+                    .collect::<Vec<_>>();
+                let snapshot = format!(
+                    r#"{{"final":true,"anchors":[{}],"alignment":[{}]}}"#,
+                    state.anchors.join(","),
+                    alignment.join(",")
+                );
+                state.anchors.clear();
+                state.associations.clear();
+                state.write("final.json", snapshot);
+            }
+            Event::AssociationRejected { .. } => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AlignedPair;
+
+    #[test]
+    fn anchors_committed_after_the_cycles_are_written() {
+        let writer = SnapshotWriter::new(vec![]);
+        let observe = |event| Observer::<&str>::observe(&writer, event);
+        observe(Event::CycleFinished {
+            cycle: 0,
+            coverage: 0.5,
+        });
+        observe(Event::AnchorCommitted {
+            a: 1,
+            b: 2,
+            score: 3,
+        });
+        observe(Event::Finished {
+            anchors: vec![AlignedPair {
+                a: 1,
+                b: 2,
+                score: 3,
+            }],
+        });
+
+        let Sink::Stream(written) = writer.state.into_inner().unwrap().sink else {
+            unreachable!()
+        };
+        let last = String::from_utf8(written)
+            .unwrap()
+            .lines()
+            .last()
+            .map(str::to_owned);
+        assert_eq!(
+            last.as_deref(),
+            Some(r#"{"final":true,"anchors":[[1,2,3]],"alignment":[[1,2,3]]}"#)
+        );
+    }
+}
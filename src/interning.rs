@@ -0,0 +1,111 @@
+use std::fmt::{self, Display};
+
+use super::hash::HashMap;
+use super::Sentence;
+
+/// A word interned in a `Vocab`, compared and hashed as a `u32` symbol rather than as a string. Symbols are
+/// stable: a word keeps its symbol for the lifetime of the `Vocab`, and symbols are assigned in the order
+/// in which words were first interned
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct InternedWord(u32);
+
+impl InternedWord {
+    /// Returns the symbol of the word
+    pub fn symbol(self) -> u32 {
+        self.0
+    }
+}
+
+/// Displays the symbol of the word, use `Vocab::resolve` for its string
+impl Display for InternedWord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{}", self.0)
+    }
+}
+
+/// A sentence of interned words
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct InternedSentence(Vec<InternedWord>);
+
+impl Sentence<InternedWord> for InternedSentence {
+    fn words(&self) -> &[InternedWord] {
+        &self.0
+    }
+}
+
+/// The string table of the `InternedWord`s, typically shared by both texts so that a word spelled the same
+/// in each receives the same symbol
+#[derive(Debug, Clone, Default)]
+pub struct Vocab {
+    strings: Vec<Box<str>>,
+    symbols: HashMap<Box<str>, InternedWord>,
+}
+
+impl Vocab {
+    /// Returns the symbol of `word`, interning it if it is new
+    pub fn intern(&mut self, word: &str) -> InternedWord {
+        if let Some(&symbol) = self.symbols.get(word) {
+            return symbol;
+        }
+
+        let symbol = InternedWord(
+            u32::try_from(self.strings.len()).expect("Vocab exceeded u32::MAX distinct words"),
+        );
+        self.strings.push(word.into());
+        self.symbols.insert(word.into(), symbol);
+        symbol
+    }
+
+    /// Returns the symbol of `word`, or `None` if it was never interned
+    pub fn get(&self, word: &str) -> Option<InternedWord> {
+        self.symbols.get(word).copied()
+    }
+
+    /// Returns the string of `word`. Panics if `word` was interned by another `Vocab`
+    pub fn resolve(&self, word: InternedWord) -> &str {
+        &self.strings[word.0 as usize]
+    }
+
+    /// Returns the number of distinct words interned
+    pub fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    /// Returns true if no word was interned
+    pub fn is_empty(&self) -> bool {
+        self.strings.is_empty()
+    }
+
+    /// Interns each of the `words` of a tokenized sentence
+    pub fn intern_sentence<S: AsRef<str>>(
+        &mut self,
+        words: impl IntoIterator<Item = S>,
+    ) -> InternedSentence {
+        InternedSentence(
+            words
+                .into_iter()
+                .map(|word| self.intern(word.as_ref()))
+                .collect(),
+        )
+    }
+
+    /// Interns each sentence of a tokenized text, e.g. `vocab.intern_text(&Tokenized::all(lines))`
+    pub fn intern_text<Word, S>(&mut self, sentences: &[S]) -> Vec<InternedSentence>
+    where
+        Word: PartialEq + AsRef<str>,
+        S: Sentence<Word>,
+    {
+        sentences
+            .iter()
+            .map(|sentence| self.intern_sentence(sentence.words()))
+            .collect()
+    }
+
+    /// Returns the strings of the words of `sentence`
+    pub fn words<'a>(
+        &'a self,
+        sentence: &'a InternedSentence,
+    ) -> impl Iterator<Item = &'a str> + 'a {
+        sentence.0.iter().map(|&word| self.resolve(word))
+    }
+}
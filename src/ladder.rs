@@ -0,0 +1,26 @@
+use std::io::{self, Write};
+
+use super::{AlignedPair, Coordinates, Output, X, Y};
+
+impl<'a, T, U> Output<'a, T, U> {
+    /// Returns the monotone path of the alignment as a ladder of rungs increasing in both texts, formed by
+    /// the anchors of the `AnchorChain`
+    pub fn ladder(&self) -> Vec<AlignedPair> {
+        self.anchor_chain()
+            .iter()
+            .map(|(a, b)| {
+                let coordinates = Coordinates(X(b), Y(a));
+                AlignedPair::new(coordinates, self.sat.score(coordinates))
+            })
+            .collect()
+    }
+
+    /// Writes the ladder to `writer` with one rung per line as tab separated `a`, `b` and `score`, followed
+    /// by a closing rung at the lengths of both texts with a score of `0`
+    pub fn export_ladder(&self, mut writer: impl Write) -> io::Result<()> {
+        for rung in self.ladder() {
+            writeln!(writer, "{}\t{}\t{}", rung.a, rung.b, rung.score)?;
+        }
+        writeln!(writer, "{}\t{}\t0", self.a.len(), self.b.len())
+    }
+}
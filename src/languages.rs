@@ -0,0 +1,71 @@
+use std::collections::HashSet;
+
+use super::{Config, FrequencyScaling, Normalization, WordFilter};
+
+/// Languages with bundled stop word lists, see `Config::with_languages`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Lang {
+    Dutch,
+    English,
+    French,
+    German,
+    Italian,
+    Portuguese,
+    Russian,
+    Spanish,
+}
+
+impl Lang {
+    /// Returns the ISO 639-1 code of the language
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::Dutch => "nl",
+            Self::English => "en",
+            Self::French => "fr",
+            Self::German => "de",
+            Self::Italian => "it",
+            Self::Portuguese => "pt",
+            Self::Russian => "ru",
+            Self::Spanish => "es",
+        }
+    }
+
+    /// Returns the stop words of the language, in lowercase
+    pub fn stopwords(self) -> &'static [&'static str] {
+        stop_words::get(self.code())
+    }
+
+    /// Returns a filter excluding the stop words of the language, ignoring case
+    pub fn stopword_filter<Word: AsRef<str>>(self) -> WordFilter<Word> {
+        let stopwords = self.stopwords().iter().copied().collect::<HashSet<_>>();
+        Box::new(move |word| !stopwords.contains(word.as_ref().to_lowercase().as_str()))
+    }
+}
+
+impl<Word: AsRef<str>> Config<Word> {
+    /// Returns a configuration for texts in the languages `a` and `b`, which lowercases words and excludes
+    /// the stop words of each language. The word frequency thresholds are derived from the occurrences of
+    /// the words of the texts (see `FrequencyScaling`), so that they are lower for inflected languages,
+    /// which spread each lemma over many rarer forms
+    pub fn with_languages(a: Lang, b: Lang) -> Self {
+        Self {
+            frequency_scaling: Some(FrequencyScaling::default()),
+            normalizer: Some(
+                Normalization {
+                    lowercase: true,
+                    ..Default::default()
+                }
+                .normalizer(),
+            ),
+            a_word_filter: Some(a.stopword_filter()),
+            b_word_filter: Some(b.stopword_filter()),
+            ..Default::default()
+        }
+    }
+
+    /// Returns a configuration for texts both in the language `lang`, e.g. different editions of one work.
+    /// See `Config::with_languages`
+    pub fn with_language(lang: Lang) -> Self {
+        Self::with_languages(lang, lang)
+    }
+}
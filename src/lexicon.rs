@@ -0,0 +1,25 @@
+use std::hash::Hash;
+
+use super::hash::{HashMap, HashSet};
+
+/// Partners of the words of text `a` among the word associations accepted while aligning
+pub(crate) type Lexicon<'a, Word> = HashMap<&'a Word, HashSet<&'a Word>>;
+
+/// Returns the fraction of the words of the smaller of `a_words` and `b_words` with a partner in the other
+/// in the `lexicon`
+pub(crate) fn support<Word: Eq + Hash>(
+    lexicon: &Lexicon<'_, Word>,
+    a_words: &HashSet<&Word>,
+    b_words: &HashSet<&Word>,
+) -> f32 {
+    let supported = a_words
+        .iter()
+        .filter(|a_word| {
+            lexicon
+                .get(*a_word)
+                .is_some_and(|partners| partners.iter().any(|b_word| b_words.contains(b_word)))
+        })
+        .count();
+
+    supported as f32 / a_words.len().min(b_words.len()).max(1) as f32
+}
@@ -1,47 +1,383 @@
 use std::cmp::Reverse;
-use std::collections::{BTreeSet, HashMap, HashSet};
+use std::collections::BTreeSet;
+use std::fmt::Debug;
 use std::hash::Hash;
+use std::num::NonZeroUsize;
+use std::ops::Range;
+use std::rc::Rc;
+use std::sync::{Arc, OnceLock};
 
+mod abridgement;
 mod alignable_sentence_table;
+mod aligned_pair;
+mod aligned_sentence;
+mod aligned_span;
+mod anchor_chain;
+mod batch;
+pub mod binary;
+mod bitext;
+mod block_moves;
+mod boilerplate;
+mod chapters;
+mod character_fallback;
+mod clauses;
+mod cleaning;
+mod corrections;
+mod coverage;
+mod decay;
+#[cfg(feature = "whatlang")]
+mod detection;
+mod differential;
+mod distribution;
+mod dot_plot;
+mod embedding;
+mod ensemble;
+mod events;
+mod exact_matches;
+mod frequency_scaling;
+mod gaps;
+mod hash;
+mod inspector;
+mod interning;
+mod ladder;
+#[cfg(feature = "stopwords")]
+mod languages;
+mod lexicon;
+pub mod mappers;
+#[cfg(feature = "ndarray")]
+mod matrix;
+mod memory;
+mod morphology;
+mod ndjson;
+mod normalization;
+mod page_breaks;
+#[cfg(feature = "rayon")]
+mod parallel;
+mod presets;
+mod progress;
+mod provenance;
+mod pruning;
+mod quality_estimation;
+mod refinement;
+mod reranking;
+mod resolution;
+mod revision;
+pub mod robustness;
 mod sentence_alignment_table;
+#[cfg(feature = "serve")]
+mod serve;
+mod shared;
+mod similarity_quantiles;
+mod snapshot;
+mod spans;
+mod stats;
+mod status;
+mod stemming;
+mod streaming;
+mod swaps;
+pub mod synthetic;
+#[cfg(feature = "threads")]
+mod threads;
+mod tie_break;
+mod token_filter;
+#[cfg(feature = "tokenize")]
+mod tokenize;
+mod translations;
+pub mod tune;
+mod uncertainty;
+mod viterbi;
 mod word_association;
 mod word_association_table;
 mod word_sentence_index;
+mod xces;
+
+pub use abridgement::{Abridgement, Skip};
+pub use aligned_pair::AlignedPair;
+pub use aligned_sentence::AlignedSentence;
+pub use aligned_span::AlignedSpan;
+pub use anchor_chain::{AnchorChain, Interval};
+pub use batch::{Batch, DocumentReport, Segmentation};
+pub use bitext::Bitext;
+pub use block_moves::{BlockMoves, Move};
+pub use boilerplate::{Boilerplate, BoilerplatePolicy};
+pub use chapters::Chapters;
+pub use character_fallback::CharacterFallback;
+pub use clauses::{AlignedClause, Boundaries, Clauses};
+pub use cleaning::{Cleaning, Issue, PairQuality};
+pub use corrections::Corrections;
+pub use coverage::{CoverageWeighting, SentenceWeight};
+pub use decay::Decay;
+#[cfg(feature = "whatlang")]
+pub use detection::{Detection, LanguageMismatch};
+pub use distribution::{Bin, ScoreSummary};
+pub use embedding::{EmbeddingProvider, Embeddings};
+pub use ensemble::{Ensemble, Measure, Signal};
+pub use events::{Event, Observer, Rejection};
+pub use exact_matches::ExactMatches;
+pub use frequency_scaling::FrequencyScaling;
+pub use gaps::{Gap, Side};
+pub use inspector::SnapshotWriter;
+pub use interning::{InternedSentence, InternedWord, Vocab};
+#[cfg(feature = "stopwords")]
+pub use languages::Lang;
+pub use memory::MemoryUsage;
+pub use morphology::Decomposed;
+pub use ndjson::JsonLines;
+pub use normalization::{ConfusionCosts, Normalization, OcrTolerance};
+pub use page_breaks::PageBreaks;
+#[cfg(feature = "indicatif")]
+pub use progress::IndicatifReporter;
+pub use progress::{Progress, ProgressReporter};
+pub use provenance::Evidence;
+pub use pruning::OutlierPruning;
+pub use quality_estimation::{QualityEstimation, QualityEstimator};
+pub use refinement::Refinement;
+pub use reranking::{CandidateAnchor, Reranker};
+pub use revision::{Change, Revision};
+#[cfg(feature = "serve")]
+pub use serve::Server;
+pub use similarity_quantiles::SimilarityQuantiles;
+pub use snapshot::Snapshot;
+pub use spans::{Spanned, SpannedPair};
+pub use stats::Stats;
+pub use status::{Partner, Status};
+pub use stemming::Stemming;
+pub use streaming::StreamingAlignment;
+pub use swaps::SwapRecovery;
+#[cfg(feature = "threads")]
+pub use threads::Threads;
+pub use tie_break::{PairOrdering, TieBreak};
+pub use token_filter::TokenFilter;
+#[cfg(feature = "tokenize")]
+pub use tokenize::{Segmenter, Tokenized};
+pub use translations::Translation;
+pub use uncertainty::UncertainPair;
+pub use viterbi::Viterbi;
 
 use alignable_sentence_table::AlignableSentenceTable;
+use hash::{HashMap, HashSet};
+use lexicon::Lexicon;
+use provenance::Provenance;
 use sentence_alignment_table::{Score, SentenceAlignmentTable};
-use word_association::WordAssociation;
+use shared::Text;
+use word_association::{Similarity, WordAssociation};
 use word_association_table::WordAssociationTable;
 use word_sentence_index::WordSentenceIndex;
 
+/// Sentences of a text detected as boilerplate, and those excluded from its index
+type Detected = (Vec<usize>, HashSet<usize>);
+
+/// Computes the co-occurrence similarities of word pairs on the given number of threads
+type Scorer<Word> = for<'w> fn(
+    &AlignableSentenceTable,
+    &WordSentenceIndex<'w, Word, Y>,
+    &WordSentenceIndex<'w, Word, X>,
+    &[(&'w Word, &'w Word)],
+    NonZeroUsize,
+) -> Vec<Similarity>;
+
+/// Settings of the index of the words of one text, borrowed from the `Config` so that the indices of both
+/// texts may be built on their own threads
+struct Indexing<'c, Word> {
+    normalizer: Option<&'c Normalizer<Word>>,
+    ocr_tolerance: &'c OcrTolerance,
+    filter: Option<&'c WordFilter<Word>>,
+    record_positions: bool,
+}
+
+impl<Word: Eq + Hash> Indexing<'_, Word> {
+    /// Returns the index of the words of `text`, omitting the `excluded` sentences
+    fn index<'a, S, Axis>(
+        &self,
+        text: &'a [S],
+        excluded: &HashSet<usize>,
+    ) -> WordSentenceIndex<'a, Word, Axis>
+    where
+        Word: 'a,
+        S: Sentence<Word>,
+        Axis: Clone + Copy + Ord + From<usize> + Into<usize>,
+    {
+        WordSentenceIndex::new(
+            Boilerplate::indexed(text, excluded),
+            self.normalizer,
+            self.ocr_tolerance,
+            self.filter,
+            self.record_positions,
+        )
+    }
+}
+
+/// Indices of the words of the texts, built ahead of the run
+struct Indices<'a, Word> {
+    a: WordSentenceIndex<'a, Word, Y>,
+    b: WordSentenceIndex<'a, Word, X>,
+    a_stemmed: Option<WordSentenceIndex<'a, Word, Y>>,
+    b_stemmed: Option<WordSentenceIndex<'a, Word, X>>,
+    scorer: Option<(Scorer<Word>, NonZeroUsize)>,
+}
+
+/// A sentence of words. The trait is dyn compatible, so that sentences of heterogeneous types may be
+/// aligned as `Box<dyn Sentence<Word>>`, or borrowed as `&dyn Sentence<Word>`
 pub trait Sentence<Word: PartialEq> {
     fn words(&self) -> &[Word];
+
+    /// Returns the range of bytes of the sentence in its original document, if known, so that the
+    /// alignment may be projected back onto the document, see `Output::spanned_pairs`. Defaults to `None`
+    fn span(&self) -> Option<Range<usize>> {
+        None
+    }
+}
+
+impl<Word: PartialEq> Sentence<Word> for [Word] {
+    fn words(&self) -> &[Word] {
+        self
+    }
+}
+
+impl<Word: PartialEq, S: Sentence<Word> + ?Sized> Sentence<Word> for &S {
+    fn words(&self) -> &[Word] {
+        (**self).words()
+    }
+
+    fn span(&self) -> Option<Range<usize>> {
+        (**self).span()
+    }
+}
+
+impl<Word: PartialEq, S: Sentence<Word> + ?Sized> Sentence<Word> for Box<S> {
+    fn words(&self) -> &[Word] {
+        (**self).words()
+    }
+
+    fn span(&self) -> Option<Range<usize>> {
+        (**self).span()
+    }
+}
+
+impl<Word: PartialEq, S: Sentence<Word> + ?Sized> Sentence<Word> for Rc<S> {
+    fn words(&self) -> &[Word] {
+        (**self).words()
+    }
+
+    fn span(&self) -> Option<Range<usize>> {
+        (**self).span()
+    }
+}
+
+impl<Word: PartialEq, S: Sentence<Word> + ?Sized> Sentence<Word> for Arc<S> {
+    fn words(&self) -> &[Word] {
+        (**self).words()
+    }
+
+    fn span(&self) -> Option<Range<usize>> {
+        (**self).span()
+    }
+}
+
+/// Words which define a graded similarity to one another (e.g. embeddings, stems or feature bundles)
+pub trait WordLike {
+    /// Returns the similarity in `[0, 1]` of `self` to `other`, or `None` if the words should only be
+    /// associated by co-occurrence. A similarity of `1` is treated like an association indicated by the
+    /// `association_mapper`, while lower similarities act as a floor for the co-occurrence similarity
+    fn similarity(&self, other: &Self) -> Option<f32>;
 }
 
 pub struct Output<'a, T, U> {
-    a: &'a [T],
-    b: &'a [U],
+    a: Text<'a, T>,
+    b: Text<'a, U>,
     a_alignments: HashMap<Y, BTreeSet<X>>,
-    b_alignments: HashMap<X, BTreeSet<Y>>,
+    b_alignments: OnceLock<HashMap<X, BTreeSet<Y>>>,
     coverage: Vec<f32>,
+    sat: SentenceAlignmentTable,
+    abridgement: Option<Abridgement>,
+    outliers: Vec<(usize, usize)>,
+    snapshots: Vec<Snapshot>,
+    provenance: HashMap<Coordinates, Vec<Evidence>>,
+    moves: Vec<Move>,
+    quality: Vec<PairQuality>,
+    translations: Vec<translations::Entry>,
+    boilerplate: (Vec<usize>, Vec<usize>),
+}
+
+// Outputs only borrow or share the texts, so they may be shared across threads whenever the sentences
+// may, and cloned regardless of the sentences. The rows of the SAT are shared between clones
+const _: () = {
+    fn assert_send_sync<T: Send + Sync>() {}
+    #[allow(dead_code)]
+    fn assert_output<T: Send + Sync, U: Send + Sync>() {
+        assert_send_sync::<Output<'_, T, U>>();
+    }
+};
+
+impl<'a, T, U> Clone for Output<'a, T, U> {
+    fn clone(&self) -> Self {
+        Self {
+            a: self.a.clone(),
+            b: self.b.clone(),
+            a_alignments: self.a_alignments.clone(),
+            b_alignments: self.b_alignments.clone(),
+            coverage: self.coverage.clone(),
+            sat: self.sat.clone(),
+            abridgement: self.abridgement,
+            outliers: self.outliers.clone(),
+            snapshots: self.snapshots.clone(),
+            provenance: self.provenance.clone(),
+            moves: self.moves.clone(),
+            quality: self.quality.clone(),
+            translations: self.translations.clone(),
+            boilerplate: self.boilerplate.clone(),
+        }
+    }
+}
+
+impl<'a, T, U> Debug for Output<'a, T, U> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Output")
+            .field("a_len", &self.a.len())
+            .field("b_len", &self.b.len())
+            .field(
+                "pairs",
+                &self.pairs().map(|pair| pair.indices()).collect::<Vec<_>>(),
+            )
+            .field("coverage", &self.coverage)
+            .field("outliers", &self.outliers)
+            .field("moves", &self.moves)
+            .finish()
+    }
 }
 
 impl<'a, T, U> Output<'a, T, U> {
-    fn new(a: &'a [T], b: &'a [U], sat: SentenceAlignmentTable, coverage: Vec<f32>) -> Self {
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        a: Text<'a, T>,
+        b: Text<'a, U>,
+        sat: SentenceAlignmentTable,
+        coverage: Vec<f32>,
+        abridgement: Option<Abridgement>,
+        outliers: Vec<(usize, usize)>,
+        snapshots: Vec<Snapshot>,
+        provenance: HashMap<Coordinates, Vec<Evidence>>,
+    ) -> Self {
         let mut a_alignments: HashMap<Y, BTreeSet<X>> = Default::default();
-        let mut b_alignments: HashMap<X, BTreeSet<Y>> = Default::default();
-
         for Coordinates(x, y) in sat.anchors() {
             a_alignments.entry(y).or_default().insert(x);
-            b_alignments.entry(x).or_default().insert(y);
         }
 
         Self {
             a,
             b,
             a_alignments,
-            b_alignments,
+            b_alignments: OnceLock::new(),
             coverage,
+            sat,
+            abridgement,
+            outliers,
+            snapshots,
+            provenance,
+            moves: vec![],
+            quality: vec![],
+            translations: vec![],
+            boilerplate: Default::default(),
         }
     }
 
@@ -59,20 +395,112 @@ impl<'a, T, U> Output<'a, T, U> {
 
     /// Returns an iterator of alignments for the sentence at index `i` of text `b`
     pub fn b_alignments(&self, i: usize) -> impl Iterator<Item = &T> {
-        self.b_alignments
+        self.b_alignment_map()
             .get(&X(i))
             .into_iter()
             .flatten()
             .map(|Y(j)| &self.a[*j])
     }
 
-    /// Returns the coverage (aligned sentences / total sentences) obtained per cycle
+    /// Returns the alignments of the sentences of text `b`, built from those of text `a` when first needed
+    fn b_alignment_map(&self) -> &HashMap<X, BTreeSet<Y>> {
+        self.b_alignments.get_or_init(|| {
+            let mut b_alignments: HashMap<X, BTreeSet<Y>> = Default::default();
+            for (&y, xs) in &self.a_alignments {
+                for &x in xs {
+                    b_alignments.entry(x).or_default().insert(y);
+                }
+            }
+
+            b_alignments
+        })
+    }
+
+    /// Returns an iterator of the aligned pairs, ordered by `a`
+    pub fn pairs(&self) -> impl Iterator<Item = AlignedPair> + '_ {
+        let mut pairs = self
+            .a_alignments
+            .iter()
+            .flat_map(|(&y, xs)| {
+                xs.iter().map(move |&x| {
+                    AlignedPair::new(Coordinates(x, y), self.sat.score(Coordinates(x, y)))
+                })
+            })
+            .collect::<Vec<_>>();
+        pairs.sort_unstable();
+        pairs.into_iter()
+    }
+
+    /// Returns the coverage (aligned sentences / total sentences, weighted by the `coverage_weighting`)
+    /// obtained per cycle
     pub fn coverage(&self) -> &[f32] {
         &self.coverage
     }
+
+    /// Returns a view of the alignment keeping only the aligned pairs with a score of at least `min_score`,
+    /// e.g. for a high precision deliverable, without aligning the texts again. Locked anchors are kept
+    pub fn filter(&self, min_score: usize) -> Self {
+        self.with_anchor_threshold(min_score.max(self.sat.anchor_threshold().0))
+    }
+
+    /// Returns the alignment at each of the anchor `thresholds` (e.g. `[5, 3, 2]`) from the scores of this
+    /// run, without aligning the texts again. Thresholds below the one the texts were aligned with admit
+    /// the pairs which had gathered some evidence without becoming anchors, which have no provenance
+    pub fn at_thresholds(&self, thresholds: &[usize]) -> Vec<Self> {
+        thresholds
+            .iter()
+            .map(|&threshold| self.with_anchor_threshold(threshold))
+            .collect()
+    }
+
+    fn with_anchor_threshold(&self, anchor_threshold: usize) -> Self {
+        let mut sat = self.sat.clone();
+        sat.set_anchor_threshold(Score(anchor_threshold.max(1)));
+        let provenance = self
+            .provenance
+            .iter()
+            .filter(|(&coordinates, _)| sat.is_anchor(coordinates))
+            .map(|(&coordinates, evidence)| (coordinates, evidence.clone()))
+            .collect();
+
+        let mut output = Self::new(
+            self.a.clone(),
+            self.b.clone(),
+            sat,
+            self.coverage.clone(),
+            self.abridgement,
+            self.outliers.clone(),
+            self.snapshots.clone(),
+            provenance,
+        );
+        output.moves = self.moves.clone();
+        output.quality = self.quality.clone();
+        output.translations = self.translations.clone();
+        output.boilerplate = self.boilerplate.clone();
+
+        output
+    }
 }
 
-type AssociationMapper<Word> = Box<dyn for<'a> Fn(&'a Word, &'a Word) -> bool>;
+/// Predicate indicating the pairs of words `(a, b)` which are associated outright, e.g. from `mappers`
+pub type AssociationMapper<Word> = Box<dyn for<'a> Fn(&'a Word, &'a Word) -> bool>;
+type WordSimilarity<Word> = Box<dyn for<'a> Fn(&'a Word, &'a Word) -> Option<f32>>;
+/// Maps a word to the normalized form under which it is indexed
+#[cfg(not(feature = "threads"))]
+pub type Normalizer<Word> = Box<dyn Fn(&Word) -> String>;
+/// Maps a word to the normalized form under which it is indexed. Shared by the threads building the
+/// indices in parallel
+#[cfg(feature = "threads")]
+pub type Normalizer<Word> = Box<dyn Fn(&Word) -> String + Send + Sync>;
+/// Decomposes a word into the morphemes or sub-word units under which it is indexed, see `Decomposed`
+pub type Analyzer<Word> = Box<dyn Fn(&Word) -> Vec<String>>;
+/// Predicate selecting the words which are indexed
+#[cfg(not(feature = "threads"))]
+pub type WordFilter<Word> = Box<dyn Fn(&Word) -> bool>;
+/// Predicate selecting the words which are indexed. Shared by the threads building the indices in
+/// parallel
+#[cfg(feature = "threads")]
+pub type WordFilter<Word> = Box<dyn Fn(&Word) -> bool + Send + Sync>;
 
 pub struct Config<Word> {
     /// Score required for an alignment to be considered an anchor and influence the AST. Defaults to `3`
@@ -87,6 +515,13 @@ pub struct Config<Word> {
     /// Floor past which the `word_frequency_threshold` will no longer be decreased by
     /// `word_frequency_taper` on subsequent cycles. Defaults to `0`
     pub word_frequency_minimum: usize,
+    /// Derivation of the `word_frequency_threshold`, `word_frequency_taper` and `word_frequency_minimum`
+    /// from the occurrences of the words of the texts, replacing the configured values. Defaults to `None`
+    pub frequency_scaling: Option<FrequencyScaling>,
+    /// Ratio of the occurrences of the more frequent word of an association to those of the less frequent
+    /// beyond which the association is rejected, so that a frequent word is not associated with a rare one
+    /// which happens to occur alongside it. Defaults to `None`
+    pub max_occurrence_ratio: Option<f32>,
     /// Word similarity required for items to be entered in the WAT. Defaults to `0.8`
     pub word_similarity_threshold: f32,
     /// The `word_similarity_threshold` will be decreased by this amount each cycle
@@ -95,12 +530,142 @@ pub struct Config<Word> {
     /// Floor past which the `word_similarity_threshold` will no longer be decreased by
     /// `word_similarity_taper` on subsequent cycles. Defaults to `0.3`
     pub word_similarity_minimum: f32,
+    /// Derivation of each cycle's word similarity threshold from the similarities of the associations
+    /// eligible in that cycle, replacing the `word_similarity_threshold` and `word_similarity_taper`. The
+    /// threshold is never below the `word_similarity_minimum`. Defaults to `None`
+    pub similarity_quantiles: Option<SimilarityQuantiles>,
+    /// Scale applied to the width of the corridors of alignable sentences between anchors. Defaults to `1.0`
+    pub corridor_width: f32,
+    /// Align the sentences of ambiguous candidates (e.g. refrains or boilerplate repeated at many indices)
+    /// by their relative position, rather than discarding the evidence of the association. Defaults to `false`
+    pub positional_disambiguation: bool,
+    /// Maximum number of word pairs each word may participate in per cycle, preferring partners of
+    /// comparable frequency and then rarer partners. Bounds the cost of building the WAT for pathological
+    /// texts. Defaults to `None`
+    pub max_candidates_per_word: Option<usize>,
+    /// Number of alignable cells in which a pair of words must co-occur for its similarity to be computed,
+    /// discarding the many pairs co-occurring by chance before they reach the scorer. Defaults to `1`
+    pub min_cooccurrences: usize,
+    /// Maximum number of associations entered in the WAT per cycle, keeping the most similar in a bounded
+    /// heap rather than ordering every association, most of which never produce an anchor. Defaults to
+    /// `None`
+    pub max_associations: Option<usize>,
+    /// Approximate memory, in bytes, which the index, SAT, AST and WAT should not exceed. Before each cycle
+    /// whose WAT is estimated to exceed it, the corridors are narrowed by half and the frequency threshold
+    /// doubled (at most 8 times), degrading the alignment rather than exhausting the memory. The usage is
+    /// reported by `Progress::memory`. Defaults to `None`
+    pub memory_limit: Option<usize>,
+    /// Gather the matches proposed by all the associations of a cycle and commit the non-crossing subset of
+    /// greatest total similarity, rather than applying the associations one at a time, where earlier
+    /// associations may block better supported anchors. As when they are applied one at a time, an
+    /// association whose matches cross the evidence of the SAT is rejected, and its matches are vetted by
+    /// the `reranker`, though against the scores at the start of the cycle. Defaults to `false`
+    pub global_resolution: bool,
+    /// Order in which associations of equal similarity are processed. Defaults to `TieBreak::Frequency`
+    pub tie_break: TieBreak<Word>,
+    /// Decay of the scores of cells below the anchor threshold which stop being reinforced. Defaults to
+    /// `None`
+    pub decay: Option<Decay>,
+    /// Tolerance of large spans of sentences omitted from either text. Defaults to `None`
+    pub abridgement: Option<Abridgement>,
+    /// Page boundaries of page-synchronized editions, guiding the corridors while the anchors are sparse.
+    /// Defaults to `None`
+    pub page_breaks: Option<PageBreaks>,
     /// Minimum coverage to reach before the alignment is considered finished. The algorithm will
     /// continue processing until either this value or the `max_cycles` is reached. Defaults to `0.95`
     pub min_coverage: f32,
+    /// Weighting of the sentences counted towards the coverage, e.g. so that aligning long sentences
+    /// matters more than aligning fragments. Defaults to `CoverageWeighting::Sentences`
+    pub coverage_weighting: CoverageWeighting<Word>,
     /// Mapper which may be used to pre-populate the WAT. Associations indicated by the mapper will be
     /// given the highest priority (a similarity score of 1 and maximum frequency). Defaults to `|_, _| false`
     pub association_mapper: AssociationMapper<Word>,
+    /// Graded similarity between words, generalizing the `association_mapper`. See `WordLike` for the
+    /// meaning of the returned values, and `Config::word_like` for populating it from a `WordLike`
+    /// implementation. Defaults to `|_, _| None`
+    pub word_similarity: WordSimilarity<Word>,
+    /// Weighted combination of similarity signals replacing the co-occurrence similarity of word
+    /// associations. The `association_mapper` and `word_similarity` still apply on top of the ensemble.
+    /// Defaults to `None`
+    pub ensemble: Option<Ensemble<Word>>,
+    /// Normalizer applied to the words of both texts during index construction. Words with the same
+    /// normalized form are treated as identical, e.g. using `Normalization::normalizer` for textual
+    /// words. Defaults to `None`
+    pub normalizer: Option<Normalizer<Word>>,
+    /// Stemmers for a first pass of cycles associating the stems of the words, before the later cycles
+    /// associate the words as normalized by the `normalizer`. Defaults to `None`
+    pub stemming: Option<Stemming<Word>>,
+    /// Filter of the words of text `a`, e.g. `TokenFilter::filter`. Words for which it returns false (e.g.
+    /// stop words or punctuation) are excluded from the index, and so from the word frequencies, associations
+    /// and word counts. Defaults to `None`
+    pub a_word_filter: Option<WordFilter<Word>>,
+    /// Filter of the words of text `b`, e.g. `TokenFilter::filter`. Words for which it returns false (e.g.
+    /// stop words or punctuation) are excluded from the index, and so from the word frequencies, associations
+    /// and word counts. Defaults to `None`
+    pub b_word_filter: Option<WordFilter<Word>>,
+    /// Detection of the sentences repeated verbatim within each text, which are kept from degrading the
+    /// rest of the alignment, see `Output::boilerplate`. Defaults to `None`
+    pub boilerplate: Option<Boilerplate>,
+    /// Predicate selecting the content words (e.g. the nouns, verbs and adjectives of a tagged corpus), which
+    /// alone are associated with one another. Unlike the word filters, the other words are still indexed, and
+    /// so still counted towards the word frequencies. Defaults to `None`
+    pub content_words: Option<WordFilter<Word>>,
+    /// Tolerance of OCR errors in the normalized words of text `a`. Defaults to no tolerance
+    pub a_ocr_tolerance: OcrTolerance,
+    /// Tolerance of OCR errors in the normalized words of text `b`. Defaults to no tolerance
+    pub b_ocr_tolerance: OcrTolerance,
+    /// Pairs of sentence indices `(a, b)` seeded as locked anchors, e.g. reviewed decisions. Locked anchors
+    /// are never removed, and cells crossing them never become candidates. Defaults to `vec![]`
+    pub locked_anchors: Vec<(usize, usize)>,
+    /// Character n-gram alignment used to seed the SAT for texts too short to produce word associations.
+    /// Defaults to `None`
+    pub character_fallback: Option<CharacterFallback<Word>>,
+    /// Pre-pass anchoring the sentences identical in both texts before the cycles. Defaults to `None`
+    pub exact_matches: Option<ExactMatches>,
+    /// Sentence embeddings contributing evidence to the SAT in addition to the word associations.
+    /// Defaults to `None`
+    pub embeddings: Option<Embeddings<Word>>,
+    /// External quality estimation blended into the scores of the cells near the anchor threshold.
+    /// Defaults to `None`
+    pub quality_estimation: Option<QualityEstimation<Word>>,
+    /// Refinement of the cells near the anchor threshold using a lexicon learned from the confident anchors,
+    /// performed once the cycles have finished. Defaults to `None`
+    pub refinement: Option<Refinement>,
+    /// Decoding of the anchors as the most probable monotone path through the SAT, replacing those reached
+    /// by thresholding its scores, performed once the cycles and refinement have finished. Defaults to
+    /// `None`
+    pub viterbi: Option<Viterbi>,
+    /// Cleanup flagging or removing the anchors isolated from their neighboring anchors, performed once the
+    /// cycles have finished. Defaults to `None`
+    pub outlier_pruning: Option<OutlierPruning>,
+    /// Recovery of adjacent sentences translated in swapped order, which the crossover check otherwise
+    /// keeps from ever being aligned, performed once the cycles have finished. Defaults to `None`
+    pub swap_recovery: Option<SwapRecovery>,
+    /// Assessment of the aligned pairs on their lengths, the associations between their words and their
+    /// likelihood of being untranslated or garbage, flagging or removing the likely misaligned pairs once the
+    /// cycles have finished, see `Output::quality`. Defaults to `None`
+    pub cleaning: Option<Cleaning>,
+    /// Detection of sections moved between the texts, aligned independently of the monotone alignment and
+    /// reported by `Output::moves`, performed once the cycles have finished. Defaults to `None`
+    pub block_moves: Option<BlockMoves>,
+    /// Hook which may veto or reorder the anchors proposed by each association before they are committed.
+    /// Defaults to `None`
+    pub reranker: Option<Reranker<Word>>,
+    /// Keep a snapshot of the SAT at the end of each cycle, see `Output::snapshots`. Defaults to `false`
+    pub snapshots: bool,
+    /// Record the word associations which incremented each pair, see `Output::provenance`. Defaults to
+    /// `false`
+    pub provenance: bool,
+    /// Induce a bilingual lexicon from the word associations accepted while aligning, see
+    /// `Output::translations`. Defaults to `false`
+    pub translations: bool,
+    /// Receives progress updates at the end of each cycle. Defaults to `None`
+    pub progress: Option<Box<dyn ProgressReporter>>,
+    /// Receives the events of the alignment as they happen. Defaults to `None`
+    pub observer: Option<Box<dyn Observer<Word>>>,
+    /// Report every association discarded to the `observer` as `Event::AssociationRejected`, with the
+    /// reason and the values involved, rather than only those proposing a crossing pair. Defaults to `false`
+    pub rejections: bool,
 }
 
 impl<Word> Default for Config<Word> {
@@ -111,11 +676,64 @@ impl<Word> Default for Config<Word> {
             word_frequency_threshold: 5,
             word_frequency_taper: 0,
             word_frequency_minimum: 0,
+            frequency_scaling: None,
+            max_occurrence_ratio: None,
             word_similarity_threshold: 0.8,
             word_similarity_taper: 0.05,
             word_similarity_minimum: 0.3,
+            similarity_quantiles: None,
+            corridor_width: 1.0,
+            positional_disambiguation: false,
+            max_candidates_per_word: None,
+            min_cooccurrences: 1,
+            max_associations: None,
+            memory_limit: None,
+            global_resolution: false,
+            tie_break: Default::default(),
+            decay: None,
+            abridgement: None,
+            page_breaks: None,
             min_coverage: 0.95,
+            coverage_weighting: Default::default(),
             association_mapper: Box::new(|_, _| false),
+            word_similarity: Box::new(|_, _| None),
+            ensemble: None,
+            normalizer: None,
+            stemming: None,
+            a_word_filter: None,
+            b_word_filter: None,
+            boilerplate: None,
+            content_words: None,
+            a_ocr_tolerance: Default::default(),
+            b_ocr_tolerance: Default::default(),
+            locked_anchors: vec![],
+            character_fallback: None,
+            exact_matches: None,
+            embeddings: None,
+            quality_estimation: None,
+            refinement: None,
+            viterbi: None,
+            outlier_pruning: None,
+            swap_recovery: None,
+            cleaning: None,
+            block_moves: None,
+            reranker: None,
+            snapshots: false,
+            provenance: false,
+            translations: false,
+            progress: None,
+            observer: None,
+            rejections: false,
+        }
+    }
+}
+
+impl<Word: WordLike> Config<Word> {
+    /// Returns the default configuration with the `word_similarity` defined by `Word`'s `WordLike` implementation
+    pub fn word_like() -> Self {
+        Self {
+            word_similarity: Box::new(|a, b| a.similarity(b)),
+            ..Default::default()
         }
     }
 }
@@ -123,31 +741,231 @@ impl<Word> Default for Config<Word> {
 impl<Word> Config<Word> {
     pub fn align<'a, T, U>(self, a: &'a [T], b: &'a [U]) -> Output<'a, T, U>
     where
-        Word: Eq + PartialOrd + Hash + std::fmt::Debug + 'a,
+        Word: Eq + Hash + 'a,
         T: Sentence<Word>,
-        U: Sentence<Word>
+        U: Sentence<Word>,
     {
-        Parallelogram {
+        self.run(a, b, None)
+    }
+
+    /// Aligns `a` and `b` starting from a SAT pre-filled with the scores of the given pairs, e.g. the
+    /// `Output::cells` of a previous run or evidence produced by another tool. Pairs outside of the texts
+    /// are ignored
+    pub fn align_from<'a, T, U>(
+        self,
+        a: &'a [T],
+        b: &'a [U],
+        cells: impl IntoIterator<Item = AlignedPair>,
+    ) -> Output<'a, T, U>
+    where
+        Word: Eq + Hash + 'a,
+        T: Sentence<Word>,
+        U: Sentence<Word>,
+    {
+        let mut sat = SentenceAlignmentTable::new(a, b, Score::from(self.anchor_threshold));
+        for pair in cells {
+            if pair.a < a.len() && pair.b < b.len() && pair.score > 0 {
+                sat.add(pair.coordinates(), Score::from(pair.score));
+            }
+        }
+
+        self.run(a, b, Some(sat))
+    }
+
+    /// Re-runs the alignment of `previous` treating the `corrections` as locked anchors and forbidden pairs.
+    /// The evidence gathered by the previous run is retained, except where it contradicts the corrections
+    pub fn realign<'a, T, U>(
+        self,
+        previous: &Output<'a, T, U>,
+        corrections: &Corrections,
+    ) -> Output<'a, T, U>
+    where
+        Word: Eq + Hash + 'a,
+        T: Sentence<Word>,
+        U: Sentence<Word>,
+    {
+        let mut sat = previous.sat.clone();
+        sat.set_anchor_threshold(Score::from(self.anchor_threshold));
+        sat.raise_locked();
+        corrections.apply(&mut sat, previous.a.len(), previous.b.len());
+
+        self.run(&previous.a, &previous.b, Some(sat))
+            .with_texts(previous.a.clone(), previous.b.clone())
+    }
+
+    fn run<'a, T, U>(
+        self,
+        a: &'a [T],
+        b: &'a [U],
+        initial: Option<SentenceAlignmentTable>,
+    ) -> Output<'a, T, U>
+    where
+        Word: Eq + Hash + 'a,
+        T: Sentence<Word>,
+        U: Sentence<Word>,
+    {
+        let boilerplate = self.boilerplate(a, b);
+        let indices = Indices {
+            a: self.a_indexing(false).index(a, &boilerplate.0 .1),
+            b: self.b_indexing(false).index(b, &boilerplate.1 .1),
+            a_stemmed: self
+                .stemming
+                .as_ref()
+                .map(|_| self.a_indexing(true).index(a, &boilerplate.0 .1)),
+            b_stemmed: self
+                .stemming
+                .as_ref()
+                .map(|_| self.b_indexing(true).index(b, &boilerplate.1 .1)),
+            scorer: None,
+        };
+
+        self.run_indexed(a, b, initial, boilerplate, indices)
+    }
+
+    /// Returns the boilerplate of texts `a` and `b`, with the sentences excluded from their indices
+    fn boilerplate<T, U>(&self, a: &[T], b: &[U]) -> (Detected, Detected)
+    where
+        Word: Eq + Hash,
+        T: Sentence<Word>,
+        U: Sentence<Word>,
+    {
+        (
+            self.boilerplate
+                .map(|boilerplate| boilerplate.detect(a))
+                .unwrap_or_default(),
+            self.boilerplate
+                .map(|boilerplate| boilerplate.detect(b))
+                .unwrap_or_default(),
+        )
+    }
+
+    /// Returns the settings of the index of the words of text `a`, or of their stems if `stemmed`
+    fn a_indexing(&self, stemmed: bool) -> Indexing<'_, Word> {
+        Indexing {
+            normalizer: match &self.stemming {
+                Some(stemming) if stemmed => Some(&stemming.a_stemmer),
+                _ => self.normalizer.as_ref(),
+            },
+            ocr_tolerance: &self.a_ocr_tolerance,
+            filter: self.a_word_filter.as_ref(),
+            record_positions: self.records_positions(),
+        }
+    }
+
+    /// Returns the settings of the index of the words of text `b`, or of their stems if `stemmed`
+    fn b_indexing(&self, stemmed: bool) -> Indexing<'_, Word> {
+        Indexing {
+            normalizer: match &self.stemming {
+                Some(stemming) if stemmed => Some(&stemming.b_stemmer),
+                _ => self.normalizer.as_ref(),
+            },
+            ocr_tolerance: &self.b_ocr_tolerance,
+            filter: self.b_word_filter.as_ref(),
+            record_positions: self.records_positions(),
+        }
+    }
+
+    fn records_positions(&self) -> bool {
+        self.ensemble
+            .as_ref()
+            .is_some_and(|ensemble| ensemble.uses_word_order())
+    }
+
+    fn run_indexed<'a, T, U>(
+        self,
+        a: &'a [T],
+        b: &'a [U],
+        initial: Option<SentenceAlignmentTable>,
+        ((a_boilerplate, a_excluded), (b_boilerplate, b_excluded)): (Detected, Detected),
+        indices: Indices<'a, Word>,
+    ) -> Output<'a, T, U>
+    where
+        Word: Eq + Hash + 'a,
+        T: Sentence<Word>,
+        U: Sentence<Word>,
+    {
+        let exact_matches = self
+            .exact_matches
+            .map(|exact_matches| exact_matches.matches(a, b, self.normalizer.as_ref()))
+            .unwrap_or_default();
+        let mut parallelogram = Parallelogram {
             anchor_threshold: Score::from(self.anchor_threshold),
             max_cycles: self.max_cycles,
             word_frequency_threshold: self.word_frequency_threshold,
             word_frequency_minimum: self.word_frequency_minimum,
             word_frequency_taper: self.word_frequency_taper,
+            max_occurrence_ratio: self.max_occurrence_ratio,
             word_similarity_threshold: self.word_similarity_threshold,
             word_similarity_taper: self.word_similarity_taper,
             word_similarity_minimum: self.word_similarity_minimum,
+            similarity_quantiles: self.similarity_quantiles,
+            corridor_width: self.corridor_width,
+            positional_disambiguation: self.positional_disambiguation,
+            max_candidates_per_word: self.max_candidates_per_word,
+            min_cooccurrences: self.min_cooccurrences,
+            max_associations: self.max_associations,
+            memory_limit: self.memory_limit,
+            global_resolution: self.global_resolution,
+            tie_break: self.tie_break,
+            decay: self.decay,
+            abridgement: self.abridgement,
+            page_hints: self
+                .page_breaks
+                .map(|page_breaks| page_breaks.hints(a.len(), b.len()))
+                .unwrap_or_default(),
             min_coverage: self.min_coverage,
-            a_word_sentence_index: WordSentenceIndex::new(
-                a.iter().map(|sentence| sentence.words()),
-            ),
-            b_word_sentence_index: WordSentenceIndex::new(
-                b.iter().map(|sentence| sentence.words()),
-            ),
+            coverage_weighting: self.coverage_weighting,
+            a_word_sentence_index: indices.a,
+            b_word_sentence_index: indices.b,
             a,
             b,
+            stemmed: indices
+                .a_stemmed
+                .zip(indices.b_stemmed)
+                .zip(self.stemming.map(|stemming| stemming.cycles))
+                .map(|((a_index, b_index), cycles)| (a_index, b_index, cycles)),
+            scorer: indices.scorer,
+            content_words: self.content_words,
             association_mapper: self.association_mapper,
+            word_similarity: self.word_similarity,
+            ensemble: self.ensemble,
+            locked_anchors: self.locked_anchors,
+            character_fallback: self.character_fallback,
+            exact_matches,
+            embeddings: self.embeddings,
+            quality_estimation: self.quality_estimation,
+            refinement: self.refinement,
+            viterbi: self.viterbi,
+            outlier_pruning: self.outlier_pruning,
+            swap_recovery: self.swap_recovery,
+            cleaning: self.cleaning,
+            boilerplate: self.boilerplate,
+            a_boilerplate,
+            b_boilerplate,
+            a_excluded,
+            b_excluded,
+            block_moves: self.block_moves,
+            reranker: self.reranker,
+            snapshots: self.snapshots,
+            provenance: self.provenance,
+            translations: self.translations,
+            progress: self.progress,
+            observer: self.observer,
+            rejections: self.rejections,
+            initial,
+        };
+        if let Some(frequency_scaling) = self.frequency_scaling {
+            (
+                parallelogram.word_frequency_threshold,
+                parallelogram.word_frequency_taper,
+                parallelogram.word_frequency_minimum,
+            ) = frequency_scaling.thresholds(
+                &parallelogram.a_word_sentence_index,
+                &parallelogram.b_word_sentence_index,
+            );
         }
-        .align()
+
+        parallelogram.align()
     }
 }
 
@@ -160,6 +978,12 @@ impl From<usize> for X {
     }
 }
 
+impl From<X> for usize {
+    fn from(X(x): X) -> Self {
+        x
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 struct Y(usize);
 
@@ -169,6 +993,12 @@ impl From<usize> for Y {
     }
 }
 
+impl From<Y> for usize {
+    fn from(Y(y): Y) -> Self {
+        y
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 struct Coordinates(X, Y);
 
@@ -189,93 +1019,1039 @@ struct Parallelogram<'a, Word, T, U> {
     max_cycles: usize,
     word_frequency_threshold: usize,
     word_frequency_taper: usize,
+    max_occurrence_ratio: Option<f32>,
     word_frequency_minimum: usize,
     word_similarity_threshold: f32,
     word_similarity_taper: f32,
     word_similarity_minimum: f32,
+    similarity_quantiles: Option<SimilarityQuantiles>,
+    corridor_width: f32,
+    positional_disambiguation: bool,
+    max_candidates_per_word: Option<usize>,
+    min_cooccurrences: usize,
+    max_associations: Option<usize>,
+    memory_limit: Option<usize>,
+    global_resolution: bool,
+    tie_break: TieBreak<Word>,
+    decay: Option<Decay>,
+    abridgement: Option<Abridgement>,
+    page_hints: Vec<Coordinates>,
     min_coverage: f32,
+    coverage_weighting: CoverageWeighting<Word>,
     a: &'a [T],
     b: &'a [U],
     a_word_sentence_index: WordSentenceIndex<'a, Word, Y>,
     b_word_sentence_index: WordSentenceIndex<'a, Word, X>,
+    /// Indices of the stems of the words, with the number of cycles for which they are used
+    #[allow(clippy::type_complexity)]
+    stemmed: Option<(
+        WordSentenceIndex<'a, Word, Y>,
+        WordSentenceIndex<'a, Word, X>,
+        usize,
+    )>,
+    /// Scorer of the similarities of the word pairs, with the number of threads it may use
+    scorer: Option<(Scorer<Word>, NonZeroUsize)>,
+    content_words: Option<WordFilter<Word>>,
     association_mapper: AssociationMapper<Word>,
+    word_similarity: WordSimilarity<Word>,
+    ensemble: Option<Ensemble<Word>>,
+    locked_anchors: Vec<(usize, usize)>,
+    character_fallback: Option<CharacterFallback<Word>>,
+    /// Cells of the sentences identical in both texts, anchored before the cycles
+    exact_matches: Vec<Coordinates>,
+    embeddings: Option<Embeddings<Word>>,
+    quality_estimation: Option<QualityEstimation<Word>>,
+    refinement: Option<Refinement>,
+    viterbi: Option<Viterbi>,
+    outlier_pruning: Option<OutlierPruning>,
+    swap_recovery: Option<SwapRecovery>,
+    cleaning: Option<Cleaning>,
+    boilerplate: Option<Boilerplate>,
+    a_boilerplate: Vec<usize>,
+    b_boilerplate: Vec<usize>,
+    /// Sentences excluded from the index as boilerplate, which are not counted towards the coverage
+    a_excluded: HashSet<usize>,
+    b_excluded: HashSet<usize>,
+    block_moves: Option<BlockMoves>,
+    reranker: Option<Reranker<Word>>,
+    snapshots: bool,
+    provenance: bool,
+    translations: bool,
+    progress: Option<Box<dyn ProgressReporter>>,
+    observer: Option<Box<dyn Observer<Word>>>,
+    rejections: bool,
+    initial: Option<SentenceAlignmentTable>,
 }
 
 impl<'a, Word, T, U> Parallelogram<'a, Word, T, U>
 where
-    Word: Eq + PartialOrd + Hash + std::fmt::Debug,
+    Word: Eq + Hash,
     T: Sentence<Word>,
-    U: Sentence<Word>
+    U: Sentence<Word>,
 {
     fn align(&self) -> Output<'a, T, U> {
-        let mut sat = SentenceAlignmentTable::new(self.a, self.b, self.anchor_threshold);
+        let mut sat = self
+            .initial
+            .clone()
+            .unwrap_or_else(|| SentenceAlignmentTable::new(self.a, self.b, self.anchor_threshold));
+        for &(a, b) in &self.locked_anchors {
+            if a < self.a.len() && b < self.b.len() {
+                sat.lock(Coordinates(X(b), Y(a)));
+            }
+        }
         let mut cycle_count = 0;
-        let mut a_aligned = HashSet::new();
-        let mut b_aligned = HashSet::new();
+        let mut a_aligned = sat.cells().map(|(c, _)| c.y()).collect::<HashSet<_>>();
+        let mut b_aligned = sat.cells().map(|(c, _)| c.x()).collect::<HashSet<_>>();
         let mut coverage = 0.0;
         let mut coverage_report = vec![];
+        let mut snapshots = vec![];
+        let mut provenance = self.provenance.then(Provenance::default);
+        let mut lexicon: Option<Lexicon<'_, Word>> = (self.block_moves.is_some()
+            || self.swap_recovery.is_some()
+            || self.cleaning.is_some()
+            || self.translations)
+            .then(HashMap::default);
+        let mut ages = decay::Ages::new();
+        let mut estimates = quality_estimation::Estimates::new();
+        let mut a_weights = self
+            .coverage_weighting
+            .weights(self.a, self.a_word_sentence_index.indexed_lengths());
+        let mut b_weights = self
+            .coverage_weighting
+            .weights(self.b, self.b_word_sentence_index.indexed_lengths());
+        for &i in &self.a_excluded {
+            a_weights[i] = 0;
+        }
+        for &i in &self.b_excluded {
+            b_weights[i] = 0;
+        }
+        let total_weight = a_weights.iter().chain(&b_weights).sum::<usize>();
 
-        while coverage < self.min_coverage && cycle_count < self.max_cycles {
-            let ast = AlignableSentenceTable::from(&sat);
+        let mut seeds = self
+            .character_fallback
+            .iter()
+            .flat_map(|fallback| fallback.seeds(self.a, self.b))
+            .chain(self.exact_matches.iter().copied())
+            .filter(|&coordinates| {
+                !sat.is_forbidden(coordinates) && !sat.crosses_locked(coordinates)
+            })
+            .map(|Coordinates(X(x), Y(y))| (y, x))
+            .collect::<Vec<_>>();
+        // The seeds of both sources may cross one another, so only a monotone subset of them is anchored,
+        // with at most one seed per sentence of text `a`
+        seeds.sort_unstable_by_key(|&(a, b)| (a, Reverse(b)));
+        seeds.dedup();
+        for (y, x) in exact_matches::increasing(seeds) {
+            let (x, y) = (X(x), Y(y));
+            sat.anchor(Coordinates(x, y));
+            a_aligned.insert(y);
+            b_aligned.insert(x);
+            self.observe(Event::AnchorCommitted {
+                a: y.0,
+                b: x.0,
+                score: sat.score(Coordinates(x, y)).0,
+            });
+        }
+
+        let embedded = self
+            .embeddings
+            .as_ref()
+            .map(|embeddings| embeddings.embed(self.a, self.b));
+        let mut embedded_evidence: HashSet<_> = HashSet::default();
+        let min_coverage = match self.abridgement {
+            // Only the sentences of one text are omitted, so at most 2(1 - r) / (2 - r) of the sentences of
+            // both texts can be covered
+            Some(abridgement) => {
+                self.min_coverage * 2.0 * (1.0 - abridgement.deletion_rate)
+                    / (2.0 - abridgement.deletion_rate)
+            }
+            None => self.min_coverage,
+        };
+
+        let mut progress = Progress {
+            max_cycles: self.max_cycles,
+            min_coverage,
+            ..Default::default()
+        };
+        if let Some(reporter) = &self.progress {
+            reporter.start(self.max_cycles);
+        }
+
+        let mut degradation = 0;
+        while coverage < min_coverage && cycle_count < self.max_cycles {
+            let ast = loop {
+                let ast = AlignableSentenceTable::new(
+                    &sat,
+                    self.corridor_width / 2u32.pow(degradation) as f32,
+                    self.abridgement.as_ref(),
+                    &self.page_hints,
+                );
+                match self.memory_limit {
+                    Some(limit)
+                        if degradation < memory::MAX_DEGRADATION
+                            && self.memory_usage(&sat, &ast, None).total() > limit =>
+                    {
+                        degradation += 1
+                    }
+                    _ => break ast,
+                }
+            };
+
+            let (similarity_threshold, mut frequency_threshold) = self.thresholds(cycle_count);
+            if degradation > 0 {
+                frequency_threshold = frequency_threshold.max(1) << degradation;
+            }
+            if let Some(provenance) = &mut provenance {
+                provenance.cycle = cycle_count;
+            }
+            let (associations, gathered) =
+                self.word_associations(&ast, cycle_count, frequency_threshold);
+            let similarity_threshold = self.similarity_threshold(
+                cycle_count,
+                &associations,
+                similarity_threshold,
+                frequency_threshold,
+            );
+            self.observe(Event::CycleStarted {
+                cycle: cycle_count,
+                similarity_threshold,
+                frequency_threshold,
+            });
 
             let wat = self.word_association_table(
-                &ast,
-                (self.word_similarity_threshold - cycle_count as f32 * self.word_similarity_taper)
-                    .max(self.word_similarity_minimum),
-                (self.word_frequency_threshold - cycle_count * self.word_frequency_taper)
-                    .max(self.word_frequency_minimum),
+                associations,
+                similarity_threshold,
+                frequency_threshold,
             );
+            progress.corridor = ast.len();
+            progress.associations = wat.len();
+            progress.memory = self.memory_usage(&sat, &ast, Some((gathered, wat.len())));
+
+            if self.global_resolution {
+                let proposed = wat
+                    .into_iter()
+                    .map(|Reverse(association)| {
+                        let matches = association.proposals(
+                            &sat,
+                            self.reranker.as_ref(),
+                            self.positional_disambiguation,
+                        );
+                        (association, matches)
+                    })
+                    .collect::<Vec<_>>();
+
+                let mut proposals: HashMap<Coordinates, u64> = HashMap::default();
+                for (association, matches) in &proposed {
+                    for &coordinates in matches.iter().flatten() {
+                        *proposals.entry(coordinates).or_default() +=
+                            u64::from(association.similarity.0);
+                    }
+                }
+                let resolved = resolution::resolve(&proposals)
+                    .into_iter()
+                    .collect::<HashSet<_>>();
+
+                for (association, matches) in proposed {
+                    // As when applied one at a time, an association conflicting anywhere is rejected
+                    let result = matches
+                        .and_then(|matches| {
+                            match matches
+                                .iter()
+                                .find(|coordinates| !resolved.contains(coordinates))
+                            {
+                                Some(&crossing) => Err(crossing),
+                                None => Ok(matches),
+                            }
+                        })
+                        .map(|matches| self.commit(&mut sat, &mut estimates, matches));
+                    let accepted = self.record(
+                        &sat,
+                        &association,
+                        result,
+                        &mut a_aligned,
+                        &mut b_aligned,
+                        provenance.as_mut(),
+                    );
+                    if let Some(lexicon) = lexicon.as_mut().filter(|_| accepted) {
+                        self.learn(lexicon, &association);
+                    }
+                }
+            } else {
+                for Reverse(association) in wat {
+                    let result = association
+                        .proposals(&sat, self.reranker.as_ref(), self.positional_disambiguation)
+                        .map(|matches| self.commit(&mut sat, &mut estimates, matches));
+                    let accepted = self.record(
+                        &sat,
+                        &association,
+                        result,
+                        &mut a_aligned,
+                        &mut b_aligned,
+                        provenance.as_mut(),
+                    );
+                    if let Some(lexicon) = lexicon.as_mut().filter(|_| accepted) {
+                        self.learn(lexicon, &association);
+                    }
+                }
+            }
+
+            if let (Some(embeddings), Some((a_embedded, b_embedded))) =
+                (&self.embeddings, &embedded)
+            {
+                for coordinates in embeddings.candidates(&ast, a_embedded, b_embedded) {
+                    if sat.is_forbidden(coordinates)
+                        || sat.crosses_locked(coordinates)
+                        || (sat.score(coordinates) == Score::ZERO && sat.crossover(coordinates))
+                        || !embedded_evidence.insert(coordinates)
+                    {
+                        continue;
+                    }
 
+                    let committed = !sat.is_anchor(coordinates);
+                    sat.add(coordinates, Score::from(embeddings.weight));
+                    a_aligned.insert(coordinates.y());
+                    b_aligned.insert(coordinates.x());
+                    if committed && sat.is_anchor(coordinates) {
+                        self.observe(Event::AnchorCommitted {
+                            a: coordinates.y().0,
+                            b: coordinates.x().0,
+                            score: sat.score(coordinates).0,
+                        });
+                    }
+                }
+            }
+
+            if let Some(decay) = &self.decay {
+                decay.apply(&mut sat, &mut ages, cycle_count);
+            }
+
+            cycle_count += 1;
+            let aligned_weight = a_aligned.iter().map(|y| a_weights[y.0]).sum::<usize>()
+                + b_aligned.iter().map(|x| b_weights[x.0]).sum::<usize>();
+            coverage = aligned_weight as f32 / total_weight as f32;
+            coverage_report.push(coverage);
+            if self.snapshots {
+                snapshots.push(Snapshot::new(cycle_count - 1, &sat));
+            }
+
+            self.observe(Event::CycleFinished {
+                cycle: cycle_count - 1,
+                coverage,
+            });
+
+            progress.cycle = cycle_count;
+            progress.coverage = coverage;
+            if let Some(reporter) = &self.progress {
+                reporter.cycle(&progress);
+            }
+        }
+
+        if let Some(refinement) = &self.refinement {
+            for Coordinates(x, y) in refinement.refine(
+                &mut sat,
+                self.a,
+                self.b,
+                &self.a_word_sentence_index,
+                &self.b_word_sentence_index,
+            ) {
+                self.observe(Event::AnchorCommitted {
+                    a: y.0,
+                    b: x.0,
+                    score: sat.score(Coordinates(x, y)).0,
+                });
+            }
+        }
+
+        if let Some(viterbi) = &self.viterbi {
+            for Coordinates(x, y) in viterbi.decode(&mut sat, self.a.len(), self.b.len()) {
+                self.observe(Event::AnchorCommitted {
+                    a: y.0,
+                    b: x.0,
+                    score: sat.score(Coordinates(x, y)).0,
+                });
+            }
+        }
+
+        let mut outliers = self
+            .outlier_pruning
+            .map(|pruning| pruning.prune(&mut sat))
+            .unwrap_or_default()
+            .into_iter()
+            .map(|Coordinates(x, y)| (y.0, x.0))
+            .collect::<Vec<_>>();
+        outliers.sort_unstable();
+
+        if let (Some(swap_recovery), Some(lexicon)) = (&self.swap_recovery, &lexicon) {
+            for Coordinates(x, y) in swap_recovery.recover(
+                &mut sat,
+                self.a,
+                self.b,
+                &self.a_word_sentence_index,
+                &self.b_word_sentence_index,
+                lexicon,
+            ) {
+                self.observe(Event::AnchorCommitted {
+                    a: y.0,
+                    b: x.0,
+                    score: sat.score(Coordinates(x, y)).0,
+                });
+            }
+        }
+
+        if let Some(boilerplate) = &self.boilerplate {
+            for Coordinates(x, y) in boilerplate.align_by_position(
+                &mut sat,
+                &self.a_boilerplate,
+                &self.b_boilerplate,
+                self.a.len(),
+                self.b.len(),
+            ) {
+                self.observe(Event::AnchorCommitted {
+                    a: y.0,
+                    b: x.0,
+                    score: sat.score(Coordinates(x, y)).0,
+                });
+            }
+        }
+
+        let quality = match (&self.cleaning, &lexicon) {
+            (Some(cleaning), Some(lexicon)) => cleaning.clean(
+                &mut sat,
+                self.a,
+                self.b,
+                &self.a_word_sentence_index,
+                &self.b_word_sentence_index,
+                lexicon,
+            ),
+            _ => vec![],
+        };
+
+        if self.observer.is_some() {
+            let mut anchors = sat
+                .anchors()
+                .map(|coordinates| AlignedPair::new(coordinates, sat.score(coordinates)))
+                .collect::<Vec<_>>();
+            anchors.sort_unstable();
+            self.observe(Event::Finished { anchors });
+        }
+
+        if let Some(reporter) = &self.progress {
+            reporter.finish(&progress);
+        }
+
+        let provenance = provenance
+            .map(|provenance| provenance.retain(|coordinates| sat.is_anchor(coordinates)))
+            .unwrap_or_default();
+
+        let mut output = Output::new(
+            Text::Borrowed(self.a),
+            Text::Borrowed(self.b),
+            sat,
+            coverage_report,
+            self.abridgement,
+            outliers,
+            snapshots,
+            provenance,
+        );
+        output.quality = quality;
+        output.boilerplate = (self.a_boilerplate.clone(), self.b_boilerplate.clone());
+        if let Some(lexicon) = lexicon.as_ref().filter(|_| self.translations) {
+            output.translations = translations::induce(
+                &output.sat,
+                self.a,
+                self.b,
+                &self.a_word_sentence_index,
+                &self.b_word_sentence_index,
+                lexicon,
+            );
+        }
+        if let (Some(block_moves), Some(lexicon)) = (&self.block_moves, &lexicon) {
+            output.moves = self.moves(block_moves, lexicon, &output);
+        }
+
+        output
+    }
+
+    /// Adds the words of `association` to the `lexicon` used to recover swaps, clean the pairs, detect
+    /// block moves and induce translations
+    fn learn(&self, lexicon: &mut Lexicon<'a, Word>, association: &WordAssociation<'_, Word>) {
+        if let (Some(a_word), Some(b_word)) = (
+            self.a_word_sentence_index.indexed(association.a),
+            self.b_word_sentence_index.indexed(association.b),
+        ) {
+            lexicon.entry(a_word).or_default().insert(b_word);
+        }
+    }
+
+    /// Returns the approximate memory held by the index, the SAT, the AST and a WAT built from the given
+    /// numbers of `(pairs, associations)`. Without them, the WAT is estimated to gather every pair of words
+    /// in the corridor, bounded by the pairs of distinct words of the texts, as the few associations
+    /// passing the thresholds are negligible in comparison
+    fn memory_usage(
+        &self,
+        sat: &SentenceAlignmentTable,
+        ast: &AlignableSentenceTable,
+        wat: Option<(usize, usize)>,
+    ) -> MemoryUsage {
+        let (pairs, associations) = wat.unwrap_or_else(|| {
+            let words = |total: usize, len: usize| total.div_ceil(len.max(1));
+            let a_words = self.a_word_sentence_index.indexed_lengths().iter().sum();
+            let b_words = self.b_word_sentence_index.indexed_lengths().iter().sum();
+            let pairs = (ast.len() * words(a_words, self.a.len()) * words(b_words, self.b.len()))
+                .min(
+                    self.a_word_sentence_index.vocabulary()
+                        * self.b_word_sentence_index.vocabulary(),
+                );
+            (pairs, 0)
+        });
+
+        MemoryUsage {
+            index: self.a_word_sentence_index.memory()
+                + self.b_word_sentence_index.memory()
+                + self.stemmed.as_ref().map_or(0, |(a_index, b_index, _)| {
+                    a_index.memory() + b_index.memory()
+                }),
+            sat: sat.memory(),
+            ast: ast.memory(),
+            wat: memory::wat_memory::<Word>(pairs, associations),
+        }
+    }
+
+    /// Returns the indices of the words of texts `a` and `b` associated in the cycle `cycle`, those of the
+    /// stems during the first cycles if configured
+    fn indices(
+        &self,
+        cycle: usize,
+    ) -> (
+        &WordSentenceIndex<'a, Word, Y>,
+        &WordSentenceIndex<'a, Word, X>,
+    ) {
+        match &self.stemmed {
+            Some((a_index, b_index, cycles)) if cycle < *cycles => (a_index, b_index),
+            _ => (&self.a_word_sentence_index, &self.b_word_sentence_index),
+        }
+    }
+
+    /// Returns the similarity and frequency thresholds of the cycle `cycle`
+    fn thresholds(&self, cycle: usize) -> (f32, usize) {
+        let similarity_threshold = (self.word_similarity_threshold
+            - cycle as f32 * self.word_similarity_taper)
+            .max(self.word_similarity_minimum);
+        let frequency_threshold = self
+            .word_frequency_threshold
+            .saturating_sub(cycle * self.word_frequency_taper)
+            .max(self.word_frequency_minimum);
+
+        (similarity_threshold, frequency_threshold)
+    }
+
+    /// Aligns the candidate moves independently of one another, densest first, keeping those covered well
+    /// enough which do not overlap a move already kept
+    fn moves(
+        &self,
+        block_moves: &BlockMoves,
+        lexicon: &Lexicon<'a, Word>,
+        output: &Output<'a, T, U>,
+    ) -> Vec<Move> {
+        let candidates = block_moves.candidates(
+            self.a,
+            self.b.len(),
+            &self.a_word_sentence_index,
+            &self.b_word_sentence_index,
+            lexicon,
+            |range| {
+                let (min, max) = range
+                    .clone()
+                    .flat_map(|i| output.a_alignments.get(&Y(i)).into_iter().flatten())
+                    .fold((usize::MAX, 0), |(min, max), &X(j)| {
+                        (min.min(j), max.max(j))
+                    });
+                (min <= max).then_some(min..max + 1)
+            },
+        );
+
+        let mut moves: Vec<Move> = vec![];
+        for (a, b) in candidates {
+            if moves.iter().any(|other| {
+                a.start < other.a.end
+                    && other.a.start < a.end
+                    && b.start < other.b.end
+                    && other.b.start < b.end
+            }) {
+                continue;
+            }
+
+            let sat = self.align_block(&a, &b);
+            let a_aligned = sat.anchors().map(|c| c.y()).collect::<HashSet<_>>();
+            let b_aligned = sat.anchors().map(|c| c.x()).collect::<HashSet<_>>();
+            let coverage = (a_aligned.len() + b_aligned.len()) as f32 / (a.len() + b.len()) as f32;
+            if coverage >= block_moves.min_coverage {
+                let mut pairs = sat
+                    .anchors()
+                    .map(|coordinates| AlignedPair::new(coordinates, sat.score(coordinates)))
+                    .collect::<Vec<_>>();
+                pairs.sort_unstable();
+                moves.push(Move { a, b, pairs });
+            }
+        }
+        moves.sort_unstable_by_key(|other| (other.a.start, other.b.start));
+
+        moves
+    }
+
+    /// Aligns the sentences of the ranges `a` and `b` of the texts on their own, as for the whole texts
+    fn align_block(&self, a: &Range<usize>, b: &Range<usize>) -> SentenceAlignmentTable {
+        let mut sat = SentenceAlignmentTable::new(self.a, self.b, self.anchor_threshold);
+        let start = Coordinates(X(b.start), Y(a.start));
+        let end = Coordinates(X(b.end - 1), Y(a.end - 1));
+
+        for cycle in 0..self.max_cycles {
+            let ast = AlignableSentenceTable::within(&sat, self.corridor_width, start, end);
+            let (similarity_threshold, frequency_threshold) = self.thresholds(cycle);
+            let (associations, _) = self.word_associations(&ast, cycle, frequency_threshold);
+            let similarity_threshold = self.similarity_threshold(
+                cycle,
+                &associations,
+                similarity_threshold,
+                frequency_threshold,
+            );
+            let wat = self.word_association_table(
+                associations,
+                similarity_threshold,
+                frequency_threshold,
+            );
             for Reverse(association) in wat {
-                for Coordinates(x, y) in association.align_sentences(&mut sat) {
+                // Rejected associations are of no consequence to the block
+                let _ = association.align_sentences(
+                    &mut sat,
+                    self.reranker.as_ref(),
+                    self.positional_disambiguation,
+                );
+            }
+
+            let aligned = sat.anchors().count();
+            if aligned >= a.len().max(b.len()) {
+                break;
+            }
+        }
+
+        sat
+    }
+
+    /// Increments the scores of the `matches` of an association, blending in the quality estimates of
+    /// their sentences, and returns the matches with their scores beforehand
+    fn commit(
+        &self,
+        sat: &mut SentenceAlignmentTable,
+        estimates: &mut quality_estimation::Estimates,
+        matches: Vec<Coordinates>,
+    ) -> Vec<(Coordinates, Score)> {
+        let before = matches
+            .iter()
+            .map(|&coordinates| sat.score(coordinates))
+            .collect::<Vec<_>>();
+        for &coordinates in &matches {
+            sat.increment(coordinates);
+        }
+        if let Some(quality_estimation) = &self.quality_estimation {
+            quality_estimation.apply(sat, estimates, &matches, |Coordinates(x, y)| {
+                (&self.a[y.0] as _, &self.b[x.0] as _)
+            });
+        }
+
+        matches.into_iter().zip(before).collect()
+    }
+
+    /// Marks the sentences of the cells accepted for `association` as aligned, and reports the result.
+    /// Returns true if the association was accepted for any cell
+    fn record(
+        &self,
+        sat: &SentenceAlignmentTable,
+        association: &WordAssociation<'_, Word>,
+        result: Result<Vec<(Coordinates, Score)>, Coordinates>,
+        a_aligned: &mut HashSet<Y>,
+        b_aligned: &mut HashSet<X>,
+        provenance: Option<&mut Provenance>,
+    ) -> bool {
+        match result {
+            Ok(matches) => {
+                if matches.is_empty() && self.rejections {
+                    self.reject(association, Rejection::Ambiguous);
+                } else if !matches.is_empty() {
+                    self.observe(Event::AssociationAccepted {
+                        a_word: association.a,
+                        b_word: association.b,
+                        similarity: association.similarity.to_f32(),
+                        pairs: matches.iter().map(|(c, _)| (c.y().0, c.x().0)).collect(),
+                    });
+                }
+
+                if let Some(provenance) = provenance {
+                    for &(coordinates, _) in &matches {
+                        self.add_evidence(provenance, association, coordinates);
+                    }
+                }
+
+                let accepted = !matches.is_empty();
+                for (coordinates @ Coordinates(x, y), before) in matches {
                     a_aligned.insert(y);
                     b_aligned.insert(x);
+                    // The quality estimates may carry a cell past the threshold in one step
+                    if before < self.anchor_threshold
+                        && sat.score(coordinates) >= self.anchor_threshold
+                    {
+                        self.observe(Event::AnchorCommitted {
+                            a: y.0,
+                            b: x.0,
+                            score: sat.score(coordinates).0,
+                        });
+                    }
                 }
+
+                accepted
+            }
+            Err(Coordinates(x, y)) => {
+                self.reject(association, Rejection::Crossover { a: y.0, b: x.0 });
+                false
             }
+        }
+    }
 
-            cycle_count += 1;
-            coverage =
-                (a_aligned.len() + b_aligned.len()) as f32 / (self.a.len() + self.b.len()) as f32;
-            coverage_report.push(coverage);
+    /// Records `association` as evidence for `coordinates`, locating the associated words by their first
+    /// occurrence in each sentence
+    fn add_evidence(
+        &self,
+        provenance: &mut Provenance,
+        association: &WordAssociation<'_, Word>,
+        coordinates @ Coordinates(x, y): Coordinates,
+    ) {
+        let (a_index, b_index) = self.indices(provenance.cycle);
+        let a_word = self.a[y.0]
+            .words()
+            .iter()
+            .position(|word| a_index.canonical(word) == association.a);
+        let b_word = self.b[x.0]
+            .words()
+            .iter()
+            .position(|word| b_index.canonical(word) == association.b);
+        if let (Some(a_word), Some(b_word)) = (a_word, b_word) {
+            provenance.add(coordinates, a_word, b_word, association.similarity.to_f32());
         }
+    }
 
-        Output::new(self.a, self.b, sat, coverage_report)
+    fn reject(&self, association: &WordAssociation<'_, Word>, reason: Rejection) {
+        self.observe(Event::AssociationRejected {
+            a_word: association.a,
+            b_word: association.b,
+            similarity: association.similarity.to_f32(),
+            a_occurrences: association.a_occurrences,
+            b_occurrences: association.b_occurrences,
+            reason,
+        });
     }
 
-    fn word_association_table(
+    fn observe(&self, event: Event<'_, Word>) {
+        if let Some(observer) = &self.observer {
+            observer.observe(event);
+        }
+    }
+
+    fn prior(&self, a: &Word, b: &Word) -> Option<f32> {
+        if (self.association_mapper)(a, b) {
+            Some(1.0)
+        } else {
+            (self.word_similarity)(a, b)
+        }
+    }
+
+    /// Returns the associations of the pairs of words in the corridor, with the number of pairs gathered
+    fn word_associations(
         &'a self,
         ast: &'a AlignableSentenceTable,
-        similarity_threshold: f32,
+        cycle: usize,
         frequency_threshold: usize,
-    ) -> WordAssociationTable<'a, Word> {
-        let mut visited = HashSet::new();
-        let mut wat = BTreeSet::new();
-
-        for Coordinates(x, y) in ast.all() {
-            for a_word in self.a[y.0].words() {
-                for b_word in self.b[x.0].words() {
-                    if !visited.contains(&(a_word, b_word)) {
-                        visited.insert((a_word, b_word));
-                        let association = WordAssociation::new(
-                            ast,
-                            &self.a_word_sentence_index,
-                            &self.b_word_sentence_index,
-                            a_word,
-                            b_word,
-                            &self.association_mapper,
-                        );
+    ) -> (Vec<WordAssociation<'a, Word>>, usize) {
+        let (a_index, b_index) = self.indices(cycle);
+        let mut pairs = vec![];
+        let is_content = |word: &Word| self.content_words.as_ref().is_none_or(|f| f(word));
+
+        // Each word of text `a` is joined once with the words of the sentences of text `b` alignable with
+        // any of its sentences, found from its postings, rather than once for each cell it occurs in. The
+        // cells in which each pair co-occurs are counted along the way
+        let rows = ast.rows();
+        let mut vocabularies: Vec<Option<Vec<&Word>>> = vec![None; self.b.len()];
+        let mut joined: HashSet<&Word> = HashSet::default();
+        let mut alignable = vec![];
+        let mut partners: HashMap<&Word, usize> = HashMap::default();
+        let mut discovered = vec![];
+        for (y, row) in rows.iter().enumerate() {
+            if row.is_empty() {
+                continue;
+            }
 
-                        if association.similarity >= similarity_threshold
-                            && association.a_occurrences >= frequency_threshold
-                            && association.b_occurrences >= frequency_threshold
-                        {
-                            wat.insert(Reverse(association));
-                        }
+            for a_word in self.a[y].words() {
+                if !a_index.contains(a_word) || !is_content(a_word) {
+                    continue;
+                }
+                let a_word = a_index.canonical(a_word);
+                if !joined.insert(a_word) {
+                    continue;
+                }
+
+                alignable.clear();
+                let mut previous = None;
+                for Y(y) in a_index.sentences(a_word) {
+                    // Sentences in which the word occurs more than once are joined once
+                    if previous.replace(y) != Some(y) {
+                        alignable.extend(rows.get(y).into_iter().flatten().cloned().flatten());
                     }
                 }
+                alignable.sort_unstable();
+
+                partners.clear();
+                discovered.clear();
+                for cells in alignable.chunk_by(|x, other| x == other) {
+                    let x = cells[0];
+                    let vocabulary = vocabularies[x].get_or_insert_with(|| {
+                        let mut seen: HashSet<&Word> = HashSet::default();
+                        self.b[x]
+                            .words()
+                            .iter()
+                            .filter(|&word| is_content(word))
+                            .map(|word| b_index.canonical(word))
+                            .filter(|&word| b_index.contains(word) && seen.insert(word))
+                            .collect()
+                    });
+                    for &b_word in vocabulary.iter() {
+                        *partners.entry(b_word).or_insert_with(|| {
+                            discovered.push(b_word);
+                            0
+                        }) += cells.len();
+                    }
+                }
+
+                pairs.extend(
+                    discovered
+                        .iter()
+                        .filter(|&b_word| partners[b_word] >= self.min_cooccurrences)
+                        .map(|&b_word| (a_word, b_word)),
+                );
             }
         }
 
-        wat
+        let gathered = pairs.len();
+
+        // Partners of comparable frequency are preferred, and among those the rarer, while partners too
+        // infrequent to enter the WAT are preferred last
+        let bounded = self.max_candidates_per_word.map(|max_candidates| {
+            let mut a_partners: HashMap<&Word, Vec<&Word>> = HashMap::default();
+            let mut b_partners: HashMap<&Word, Vec<&Word>> = HashMap::default();
+            for &(a_word, b_word) in &pairs {
+                a_partners.entry(a_word).or_default().push(b_word);
+                b_partners.entry(b_word).or_default().push(a_word);
+            }
+
+            let mut a_bounded: HashSet<_> = HashSet::default();
+            for (a_word, mut partners) in a_partners {
+                let own = a_index.occurrences(a_word);
+                partners.sort_by_key(|b_word| {
+                    let occurrences = b_index.occurrences(b_word);
+                    (
+                        occurrences < frequency_threshold,
+                        occurrences.abs_diff(own),
+                        occurrences,
+                    )
+                });
+                a_bounded.extend(
+                    partners
+                        .into_iter()
+                        .take(max_candidates)
+                        .map(|b_word| (a_word, b_word)),
+                );
+            }
+            let mut b_bounded: HashSet<_> = HashSet::default();
+            for (b_word, mut partners) in b_partners {
+                let own = b_index.occurrences(b_word);
+                partners.sort_by_key(|a_word| {
+                    let occurrences = a_index.occurrences(a_word);
+                    (
+                        occurrences < frequency_threshold,
+                        occurrences.abs_diff(own),
+                        occurrences,
+                    )
+                });
+                b_bounded.extend(
+                    partners
+                        .into_iter()
+                        .take(max_candidates)
+                        .map(|a_word| (a_word, b_word)),
+                );
+            }
+
+            a_bounded
+                .intersection(&b_bounded)
+                .copied()
+                .collect::<HashSet<_>>()
+        });
+
+        // The similarity is only computed for the pairs passing the thresholds on their occurrences, unless
+        // the rejected pairs are reported with their similarity
+        let mapped = |prior: Option<f32>| prior.is_some_and(|similarity| similarity >= 1.0);
+        let candidates = pairs
+            .into_iter()
+            .enumerate()
+            .filter(|(_, pair)| {
+                bounded
+                    .as_ref()
+                    .is_none_or(|bounded| bounded.contains(pair))
+            })
+            .filter_map(|(order, (a_word, b_word))| {
+                let prior = self.prior(a_word, b_word);
+                if !mapped(prior)
+                    && !self.rejections
+                    && self
+                        .occurrence_rejection(
+                            a_index.occurrences(a_word),
+                            b_index.occurrences(b_word),
+                            frequency_threshold,
+                        )
+                        .is_some()
+                {
+                    return None;
+                }
+
+                Some((order, a_word, b_word, prior))
+            })
+            .collect::<Vec<_>>();
+
+        // With a scorer, the co-occurrence similarities are computed ahead on its threads
+        let mut computed = match self.scorer {
+            Some((scorer, threads)) if self.ensemble.is_none() => {
+                let scored = candidates
+                    .iter()
+                    .filter(|&&(.., prior)| !mapped(prior))
+                    .map(|&(_, a_word, b_word, _)| (a_word, b_word))
+                    .collect::<Vec<_>>();
+                scorer(ast, a_index, b_index, &scored, threads)
+            }
+            _ => vec![],
+        }
+        .into_iter();
+        let associations = candidates
+            .into_iter()
+            .map(|(order, a_word, b_word, prior)| {
+                WordAssociation::new(
+                    ast,
+                    a_index,
+                    b_index,
+                    a_word,
+                    b_word,
+                    order,
+                    prior,
+                    (!mapped(prior)).then(|| computed.next()).flatten(),
+                    self.ensemble.as_ref(),
+                )
+            })
+            .collect();
+
+        (associations, gathered)
+    }
+
+    /// Returns the similarity threshold of the cycle `cycle` for its `associations`, given the threshold
+    /// of the taper
+    fn similarity_threshold(
+        &self,
+        cycle: usize,
+        associations: &[WordAssociation<'a, Word>],
+        similarity_threshold: f32,
+        frequency_threshold: usize,
+    ) -> f32 {
+        let Some(similarity_quantiles) = self.similarity_quantiles else {
+            return similarity_threshold;
+        };
+
+        // Only the associations rejected for nothing but their similarity make up the distribution
+        similarity_quantiles
+            .threshold(
+                cycle,
+                associations
+                    .iter()
+                    .filter(|association| {
+                        self.rejection(association, 0.0, frequency_threshold)
+                            .is_none()
+                    })
+                    .map(|association| association.similarity.to_f32()),
+            )
+            .map_or(similarity_threshold, |threshold| {
+                threshold.max(self.word_similarity_minimum)
+            })
+    }
+
+    /// Returns the reason for which `association` is kept out of the WAT under the thresholds, if any
+    fn rejection(
+        &self,
+        association: &WordAssociation<'a, Word>,
+        similarity_threshold: f32,
+        frequency_threshold: usize,
+    ) -> Option<Rejection> {
+        if association.similarity < Similarity::from(similarity_threshold) {
+            Some(Rejection::Similarity {
+                threshold: similarity_threshold,
+            })
+        } else {
+            self.occurrence_rejection(
+                association.a_occurrences,
+                association.b_occurrences,
+                frequency_threshold,
+            )
+        }
+    }
+
+    /// Returns the reason for rejecting an association of words with the given occurrences, if any,
+    /// which is known before computing its similarity
+    fn occurrence_rejection(
+        &self,
+        a_occurrences: usize,
+        b_occurrences: usize,
+        frequency_threshold: usize,
+    ) -> Option<Rejection> {
+        if a_occurrences < frequency_threshold || b_occurrences < frequency_threshold {
+            Some(Rejection::Frequency {
+                threshold: frequency_threshold,
+            })
+        } else {
+            self.max_occurrence_ratio
+                .filter(|&max_ratio| {
+                    let (a, b) = (a_occurrences, b_occurrences);
+                    a.max(b) as f32 > max_ratio * a.min(b) as f32
+                })
+                .map(|max_ratio| Rejection::OccurrenceRatio { max_ratio })
+        }
+    }
+
+    /// Builds the WAT of the `associations` passing the thresholds, ranked
+    fn word_association_table(
+        &self,
+        associations: Vec<WordAssociation<'a, Word>>,
+        similarity_threshold: f32,
+        frequency_threshold: usize,
+    ) -> WordAssociationTable<'a, Word> {
+        let mut associations = associations
+            .into_iter()
+            .filter(|association| {
+                let rejection =
+                    self.rejection(association, similarity_threshold, frequency_threshold);
+                if let Some(reason) = rejection.filter(|_| self.rejections) {
+                    self.reject(association, reason);
+                }
+                rejection.is_none()
+            })
+            .collect::<Vec<_>>();
+        self.tie_break.rank(&mut associations);
+
+        match self.max_associations {
+            Some(k) => word_association_table::top(associations, k),
+            None => associations.into_iter().map(Reverse).collect(),
+        }
     }
 }
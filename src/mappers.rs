@@ -0,0 +1,178 @@
+//! Ready-made `Config::association_mapper`s for closely related languages or same-language alignment
+
+use std::collections::HashMap;
+
+#[cfg(feature = "transliteration")]
+use super::ensemble::transliterate;
+use super::{AssociationMapper, Normalization};
+
+/// Associates identical words
+pub fn identity<Word: PartialEq>() -> AssociationMapper<Word> {
+    Box::new(|a, b| a == b)
+}
+
+/// Associates words which are identical once lowercased
+pub fn case_insensitive<Word: AsRef<str>>() -> AssociationMapper<Word> {
+    Box::new(|a, b| {
+        a.as_ref()
+            .chars()
+            .flat_map(char::to_lowercase)
+            .eq(b.as_ref().chars().flat_map(char::to_lowercase))
+    })
+}
+
+/// Associates words which are identical once punctuation is removed, e.g. "«Adieu»" and "Adieu!". Words
+/// consisting only of punctuation are not associated
+pub fn strip_punctuation<Word: AsRef<str>>() -> AssociationMapper<Word> {
+    Box::new(|a, b| {
+        let strip = |word: &str| {
+            word.chars()
+                .filter(|c| !c.is_ascii_punctuation() && !is_punctuation(*c))
+                .collect::<String>()
+        };
+        let a = strip(a.as_ref());
+        !a.is_empty() && a == strip(b.as_ref())
+    })
+}
+
+/// Associates words whose forms under `normalization` are identical
+pub fn normalized<Word: AsRef<str>>(normalization: Normalization) -> AssociationMapper<Word> {
+    Box::new(move |a, b| normalization.apply(a.as_ref()) == normalization.apply(b.as_ref()))
+}
+
+/// Associates words which are identical once transliterated to lowercase Latin letters, e.g. "Москва" and
+/// "Moskva" or "Αθήνα" and "Athena", so that names written in different scripts can seed associations
+#[cfg(feature = "transliteration")]
+pub fn transliterated<Word: AsRef<str>>() -> AssociationMapper<Word> {
+    Box::new(|a, b| {
+        let a = transliterate(a.as_ref());
+        a.chars().any(char::is_alphanumeric) && a == transliterate(b.as_ref())
+    })
+}
+
+/// Associates words denoting the same number, whether written in digits ("14"), Roman numerals ("XIV")
+/// or words of the `number_words` table ("fourteen"), e.g. `english_number_words`. Hyphenated words are
+/// read as the sum of their parts ("twenty-one"). Roman numerals must be uppercase and of at least two
+/// letters, since others are indistinguishable from words such as "I" or "mix"
+pub fn numerals<Word: AsRef<str>>(number_words: HashMap<String, u64>) -> AssociationMapper<Word> {
+    Box::new(move |a, b| {
+        let a = numeral(a.as_ref(), &number_words);
+        a.is_some() && a == numeral(b.as_ref(), &number_words)
+    })
+}
+
+/// Table of the English number words, for use with `numerals`
+pub fn english_number_words() -> HashMap<String, u64> {
+    [
+        "zero",
+        "one",
+        "two",
+        "three",
+        "four",
+        "five",
+        "six",
+        "seven",
+        "eight",
+        "nine",
+        "ten",
+        "eleven",
+        "twelve",
+        "thirteen",
+        "fourteen",
+        "fifteen",
+        "sixteen",
+        "seventeen",
+        "eighteen",
+        "nineteen",
+    ]
+    .into_iter()
+    .zip(0..)
+    .chain(
+        [
+            "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+        ]
+        .into_iter()
+        .zip((20..).step_by(10)),
+    )
+    .chain([("hundred", 100), ("thousand", 1000)])
+    .map(|(word, n)| (word.to_owned(), n))
+    .collect()
+}
+
+/// Returns the number denoted by `word`, ignoring surrounding punctuation
+fn numeral(word: &str, number_words: &HashMap<String, u64>) -> Option<u64> {
+    let word = word.trim_matches(|c: char| c.is_ascii_punctuation() || is_punctuation(c));
+    if word.is_empty() {
+        return None;
+    }
+    if word.bytes().all(|b| b.is_ascii_digit()) {
+        return word.parse().ok();
+    }
+    if let Some(n) = roman(word) {
+        return Some(n);
+    }
+
+    let word = word.to_lowercase();
+    number_words.get(&word).copied().or_else(|| {
+        word.contains('-')
+            .then(|| {
+                word.split('-')
+                    .map(|part| number_words.get(part))
+                    .sum::<Option<u64>>()
+            })
+            .flatten()
+    })
+}
+
+/// Returns the value of an uppercase Roman numeral of at least two letters
+fn roman(word: &str) -> Option<u64> {
+    if word.len() < 2 {
+        return None;
+    }
+
+    let values = word
+        .bytes()
+        .map(|b| match b {
+            b'I' => Some(1),
+            b'V' => Some(5),
+            b'X' => Some(10),
+            b'L' => Some(50),
+            b'C' => Some(100),
+            b'D' => Some(500),
+            b'M' => Some(1000),
+            _ => None,
+        })
+        .collect::<Option<Vec<u64>>>()?;
+    let mut n = 0;
+    for (i, &value) in values.iter().enumerate() {
+        if values.get(i + 1).is_some_and(|&next| next > value) {
+            n -= value as i64;
+        } else {
+            n += value as i64;
+        }
+    }
+
+    (n > 0).then_some(n as u64)
+}
+
+/// Punctuation outside of ASCII, such as quotation marks and dashes
+fn is_punctuation(c: char) -> bool {
+    matches!(
+        c,
+        '«' | '»'
+            | '‹'
+            | '›'
+            | '“'
+            | '”'
+            | '„'
+            | '‘'
+            | '’'
+            | '‚'
+            | '–'
+            | '—'
+            | '…'
+            | '¡'
+            | '¿'
+            | '·'
+    )
+}
@@ -0,0 +1,16 @@
+use ndarray::Array2;
+
+use super::{Coordinates, Output};
+
+impl<'a, T, U> Output<'a, T, U> {
+    /// Returns the dense matrix of SAT scores, with rows indexed by the sentences of text `a` and
+    /// columns by the sentences of text `b`
+    pub fn to_matrix(&self) -> Array2<usize> {
+        let mut matrix = Array2::zeros((self.a.len(), self.b.len()));
+        for (Coordinates(x, y), score) in self.sat.cells() {
+            matrix[[y.0, x.0]] = score.0;
+        }
+
+        matrix
+    }
+}
@@ -0,0 +1,34 @@
+use std::cmp::Reverse;
+use std::mem::size_of;
+
+use super::WordAssociation;
+
+/// Approximate memory held by the structures of an alignment, in bytes
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryUsage {
+    /// Word-sentence indices of both texts
+    pub index: usize,
+    /// Sentence alignment table
+    pub sat: usize,
+    /// Alignable sentence table of the cycle
+    pub ast: usize,
+    /// Word association table of the cycle, with the word pairs gathered to build it
+    pub wat: usize,
+}
+
+impl MemoryUsage {
+    pub fn total(&self) -> usize {
+        self.index + self.sat + self.ast + self.wat
+    }
+}
+
+/// Maximum number of times the corridors are narrowed and the frequency threshold raised to stay within
+/// the memory limit
+pub(crate) const MAX_DEGRADATION: u32 = 8;
+
+/// Returns the approximate memory held by a WAT of `associations` associations built from `pairs` pairs
+/// of words, which are gathered in a list and a set
+pub(crate) fn wat_memory<Word>(pairs: usize, associations: usize) -> usize {
+    pairs * 3 * size_of::<(&Word, &Word)>()
+        + associations * size_of::<Reverse<WordAssociation<'_, Word>>>()
+}
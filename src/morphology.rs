@@ -0,0 +1,86 @@
+use std::ops::Range;
+
+use super::{Analyzer, Sentence};
+
+/// A sentence whose words were decomposed into morphemes or sub-word units (e.g. by a morphological
+/// analyzer or a BPE model), which are indexed in place of the words. In agglutinative languages the full
+/// forms of a word are each too rare to be associated, while its root and suffixes recur across the text.
+/// The morphemes retain the offsets of the words they came from, so that the offsets reported in the
+/// `Evidence` of an alignment map back to the original words through `Decomposed::word`, and the
+/// `Sentence::span` of the original sentence is kept
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Decomposed {
+    morphemes: Vec<String>,
+    words: Vec<usize>,
+    span: Option<Range<usize>>,
+}
+
+impl Decomposed {
+    /// Decomposes each word of `sentence` with the `analyzer`. A word decomposed into no morphemes is not
+    /// indexed
+    pub fn new<Word: PartialEq>(sentence: &impl Sentence<Word>, analyzer: &Analyzer<Word>) -> Self {
+        let (morphemes, words) = sentence
+            .words()
+            .iter()
+            .enumerate()
+            .flat_map(|(i, word)| {
+                analyzer(word)
+                    .into_iter()
+                    .map(move |morpheme| (morpheme, i))
+            })
+            .unzip();
+
+        Self {
+            morphemes,
+            words,
+            span: sentence.span(),
+        }
+    }
+
+    /// Decomposes each of the `sentences` of a text with the `analyzer`
+    pub fn all<Word: PartialEq, S: Sentence<Word>>(
+        sentences: &[S],
+        analyzer: &Analyzer<Word>,
+    ) -> Vec<Self> {
+        sentences
+            .iter()
+            .map(|sentence| Self::new(sentence, analyzer))
+            .collect()
+    }
+
+    /// Returns the offset in the original sentence of the word from which the morpheme at offset
+    /// `morpheme` came
+    pub fn word(&self, morpheme: usize) -> usize {
+        self.words[morpheme]
+    }
+
+    /// Returns the offsets of the morphemes of the word at offset `word` in the original sentence
+    pub fn morphemes(&self, word: usize) -> Range<usize> {
+        let start = self.words.partition_point(|&w| w < word);
+        let end = self.words.partition_point(|&w| w <= word);
+
+        start..end
+    }
+
+    /// Returns an analyzer splitting each word into its lowercased first `len` characters and the rest
+    /// of the word, a crude but language independent approximation of a root followed by its suffixes
+    pub fn root_and_suffix<Word: AsRef<str>>(len: usize) -> Analyzer<Word> {
+        Box::new(move |word: &Word| {
+            let word = word.as_ref().to_lowercase();
+            match word.char_indices().nth(len) {
+                Some((split, _)) => vec![word[..split].to_owned(), word[split..].to_owned()],
+                None => vec![word],
+            }
+        })
+    }
+}
+
+impl Sentence<String> for Decomposed {
+    fn words(&self) -> &[String] {
+        &self.morphemes
+    }
+
+    fn span(&self) -> Option<Range<usize>> {
+        self.span.clone()
+    }
+}
@@ -0,0 +1,92 @@
+use std::io::Write;
+use std::sync::Mutex;
+
+use super::{Event, Observer};
+
+/// Observer writing the progress of an alignment to a writer as newline-delimited JSON, one object per
+/// cycle started, anchor committed and cycle finished and a last one when the alignment finishes, so that
+/// long alignments may be monitored as they run. Each line is flushed as it is written, and errors
+/// writing are ignored
+pub struct JsonLines<W> {
+    state: Mutex<State<W>>,
+}
+
+struct State<W> {
+    writer: W,
+    anchors: usize,
+}
+
+impl<W: Write> JsonLines<W> {
+    /// Creates an observer writing to `writer`, for use as the `Config::observer`
+    pub fn new(writer: W) -> Self {
+        Self {
+            state: Mutex::new(State { writer, anchors: 0 }),
+        }
+    }
+}
+
+impl<W: Write, Word> Observer<Word> for JsonLines<W> {
+    fn observe(&self, event: Event<'_, Word>) {
+        let Ok(mut state) = self.state.lock() else {
+            return;
+        };
+
+        let line = match event {
+            Event::CycleStarted {
+                cycle,
+                similarity_threshold,
+                frequency_threshold,
+            } => {
+                state.anchors = 0;
+                format!(
+                    r#"{{"event":"cycle_started","cycle":{cycle},"similarity_threshold":{},"frequency_threshold":{frequency_threshold}}}"#,
+                    number(similarity_threshold)
+                )
+            }
+            Event::AnchorCommitted { a, b, score } => {
+                state.anchors += 1;
+                format!(r#"{{"event":"anchor_committed","a":{a},"b":{b},"score":{score}}}"#)
+            }
+            Event::CycleFinished { cycle, coverage } => format!(
+                r#"{{"event":"cycle_finished","cycle":{cycle},"coverage":{},"anchors":{}}}"#,
+                number(coverage),
+                state.anchors
+            ),
+            Event::Finished { anchors } => {
+                format!(r#"{{"event":"finished","anchors":{}}}"#, anchors.len())
+            }
+            _ => return,
+        };
+
+        let _ = writeln!(state.writer, "{line}").and_then(|_| state.writer.flush());
+    }
+}
+
+/// Formats `value` as a JSON number, which may not be infinite or NaN
+pub(crate) fn number(value: f32) -> String {
+    if value.is_finite() {
+        value.to_string()
+    } else {
+        "null".to_owned()
+    }
+}
+
+/// Formats `value` as a JSON string
+pub(crate) fn string(value: &str) -> String {
+    let mut string = String::with_capacity(value.len() + 2);
+    string.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => string.push_str("\\\""),
+            '\\' => string.push_str("\\\\"),
+            '\n' => string.push_str("\\n"),
+            '\r' => string.push_str("\\r"),
+            '\t' => string.push_str("\\t"),
+            c if c.is_control() => string.push_str(&format!("\\u{:04x}", c as u32)),
+            c => string.push(c),
+        }
+    }
+    string.push('"');
+
+    string
+}
@@ -0,0 +1,168 @@
+use std::collections::HashMap;
+
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+use super::Normalizer;
+
+/// Normalization options for textual words, applied during index construction without modifying the
+/// sentences themselves. Words with the same normalized form are treated as identical by the algorithm
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Normalization {
+    /// Lowercase words according to Unicode, so that e.g. "Straße" and "straße" are identical.
+    /// Defaults to `false`
+    pub lowercase: bool,
+    /// Strip combining marks so that e.g. "élève" and "eleve" are identical. Defaults to `false`
+    pub fold_diacritics: bool,
+}
+
+impl Normalization {
+    /// Returns the normalized form of `word`
+    pub fn apply(&self, word: &str) -> String {
+        let word = if self.lowercase {
+            word.to_lowercase()
+        } else {
+            word.to_owned()
+        };
+
+        if self.fold_diacritics {
+            word.nfd().filter(|c| !is_combining_mark(*c)).collect()
+        } else {
+            word
+        }
+    }
+
+    /// Returns a normalizer applying these options, for use as the `Config::normalizer`
+    pub fn normalizer<Word: AsRef<str>>(self) -> Normalizer<Word> {
+        Box::new(move |word| self.apply(word.as_ref()))
+    }
+}
+
+/// Character sequences commonly confused by OCR, and the canonical form each is replaced by
+const OCR_CONFUSIONS: &[(&str, &str)] = &[
+    ("rn", "m"),
+    ("vv", "w"),
+    ("ſ", "s"),
+    ("0", "o"),
+    ("1", "l"),
+    ("|", "l"),
+];
+
+/// Tolerance of OCR errors in the words of one text. Applies to the normalized forms of words, so requires
+/// a `Config::normalizer` to be configured
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct OcrTolerance {
+    /// Replace character sequences commonly confused by OCR (e.g. "rn" and "m", "0" and "o") by a canonical
+    /// form. Defaults to `false`
+    pub confusions: bool,
+    /// Treat words within this edit distance of a more frequent word as identical to it. Defaults to `0`
+    pub max_edit_distance: usize,
+    /// Minimum length in characters of words matched by edit distance, since short words are too easily
+    /// confused with one another. Defaults to `0`
+    pub min_length: usize,
+    /// Costs of the substitutions of characters in the edit distance, so that the errors known to be made by
+    /// a particular scanner count as less than a whole edit. Defaults to every substitution costing `1`
+    pub confusion_costs: ConfusionCosts,
+}
+
+impl OcrTolerance {
+    pub(crate) fn canonicalize(&self, mut word: String) -> String {
+        if self.confusions {
+            for (confusion, canonical) in OCR_CONFUSIONS {
+                if word.contains(confusion) {
+                    word = word.replace(confusion, canonical);
+                }
+            }
+        }
+
+        word
+    }
+
+    /// Returns true if `a` and `b` are close enough to be treated as identical
+    pub(crate) fn tolerates(&self, a: &[char], b: &[char]) -> bool {
+        self.max_edit_distance > 0
+            && a.len().min(b.len()) >= self.min_length
+            && a.len().abs_diff(b.len()) <= self.max_edit_distance
+            && self
+                .confusion_costs
+                .distance(a, b, self.max_edit_distance as f32)
+                <= self.max_edit_distance as f32
+    }
+}
+
+/// Costs in `[0, 1]` of substituting characters for one another in the edit distance of words, e.g. the
+/// confusions of an OCR engine such as "e" and "c" or "l" and "1". Substitutions without a cost cost `1`,
+/// as do insertions and deletions
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ConfusionCosts {
+    costs: HashMap<(char, char), f32>,
+}
+
+impl ConfusionCosts {
+    /// Returns the costs with the substitution of `a` and `b` for one another costing `cost`
+    pub fn with(mut self, a: char, b: char, cost: f32) -> Self {
+        self.insert(a, b, cost);
+        self
+    }
+
+    /// Sets the cost of substituting `a` and `b` for one another to `cost`
+    pub fn insert(&mut self, a: char, b: char, cost: f32) {
+        self.costs
+            .insert((a.min(b), a.max(b)), cost.clamp(0.0, 1.0));
+    }
+
+    /// Returns the cost of substituting `b` for `a`
+    pub fn cost(&self, a: char, b: char) -> f32 {
+        if a == b {
+            0.0
+        } else {
+            self.costs
+                .get(&(a.min(b), a.max(b)))
+                .copied()
+                .unwrap_or(1.0)
+        }
+    }
+
+    /// Edit distance between `a` and `b` with substitutions weighted by their costs, stopping early once it
+    /// exceeds `bound`
+    pub(crate) fn distance(&self, a: &[char], b: &[char], bound: f32) -> f32 {
+        if self.costs.is_empty() {
+            return edit_distance(a, b, bound.ceil() as usize) as f32;
+        }
+
+        let mut previous = (0..=b.len()).map(|j| j as f32).collect::<Vec<_>>();
+        for (i, &a) in a.iter().enumerate() {
+            let mut current = vec![(i + 1) as f32; b.len() + 1];
+            for (j, &b) in b.iter().enumerate() {
+                current[j + 1] = (previous[j] + self.cost(a, b))
+                    .min(previous[j + 1] + 1.0)
+                    .min(current[j] + 1.0);
+            }
+            if current.iter().all(|&distance| distance > bound) {
+                return bound + 1.0;
+            }
+            previous = current;
+        }
+
+        previous[b.len()]
+    }
+}
+
+/// Levenshtein distance between `a` and `b`, stopping early once it exceeds `bound`
+pub(crate) fn edit_distance(a: &[char], b: &[char], bound: usize) -> usize {
+    let mut previous = (0..=b.len()).collect::<Vec<_>>();
+    for (i, a) in a.iter().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, b) in b.iter().enumerate() {
+            current[j + 1] = (previous[j] + usize::from(a != b))
+                .min(previous[j + 1] + 1)
+                .min(current[j] + 1);
+        }
+        if current.iter().all(|&distance| distance > bound) {
+            return bound + 1;
+        }
+        previous = current;
+    }
+
+    previous[b.len()]
+}
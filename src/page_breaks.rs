@@ -0,0 +1,60 @@
+use super::{Coordinates, X, Y};
+
+/// Page boundaries of page-synchronized editions, such as scanned books. Pages of the same rank are
+/// presumed to correspond, so the starts of corresponding pages guide the corridors of alignable
+/// sentences like anchors until contradicted by an actual anchor
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct PageBreaks {
+    /// Indices of the first sentence of each page of text `a` after the first, in increasing order
+    pub a: Vec<usize>,
+    /// Indices of the first sentence of each page of text `b` after the first, in increasing order
+    pub b: Vec<usize>,
+}
+
+impl PageBreaks {
+    /// Returns the starts of corresponding pages which strictly follow one another in both texts, of
+    /// `a_len` and `b_len` sentences. Breaks past the end of either text are ignored
+    pub(crate) fn hints(&self, a_len: usize, b_len: usize) -> Vec<Coordinates> {
+        let mut hints: Vec<Coordinates> = vec![];
+        for (&a, &b) in self.a.iter().zip(&self.b) {
+            if a >= a_len || b >= b_len {
+                continue;
+            }
+            if hints
+                .last()
+                .is_none_or(|last| last.x().0 < b && last.y().0 < a)
+            {
+                hints.push(Coordinates(X(b), Y(a)));
+            }
+        }
+
+        hints
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Config;
+
+    #[test]
+    fn breaks_past_the_end_are_ignored() {
+        let page_breaks = PageBreaks {
+            a: vec![5, 50],
+            b: vec![5, 50],
+        };
+        assert_eq!(page_breaks.hints(20, 20), [Coordinates(X(5), Y(5))]);
+
+        let words = ["alpha", "beta", "gamma", "delta", "epsilon"];
+        let text = (0..20)
+            .map(|i| vec![words[i % words.len()], words[(i + 1) % words.len()]])
+            .collect::<Vec<_>>();
+        let text = text.iter().map(Vec::as_slice).collect::<Vec<_>>();
+        let output = Config {
+            page_breaks: Some(page_breaks),
+            ..Default::default()
+        }
+        .align(&text, &text);
+        assert!(output.pairs().all(|pair| pair.a < 20 && pair.b < 20));
+    }
+}
@@ -0,0 +1,15 @@
+use rayon::prelude::*;
+
+use super::{AlignedPair, Coordinates, Output};
+
+impl<'a, T, U> Output<'a, T, U> {
+    /// Returns a parallel iterator of the aligned pairs. Unlike `Output::pairs`, the pairs are not
+    /// ordered
+    pub fn par_pairs(&self) -> impl ParallelIterator<Item = AlignedPair> + '_ {
+        let sat = &self.sat;
+        self.a_alignments.par_iter().flat_map_iter(move |(&y, xs)| {
+            xs.iter()
+                .map(move |&x| AlignedPair::new(Coordinates(x, y), sat.score(Coordinates(x, y))))
+        })
+    }
+}
@@ -0,0 +1,98 @@
+use super::{mappers, Config};
+
+/// Named configurations tuned for common kinds of material. Each preset may be further adjusted using
+/// struct update syntax, e.g. `Config { max_cycles: 10, ..Config::novel() }`
+impl<Word> Config<Word> {
+    /// Literary translations: long texts with free, occasionally reordered renderings
+    pub fn novel() -> Self {
+        Self {
+            anchor_threshold: 3,
+            max_cycles: 25,
+            word_frequency_threshold: 5,
+            word_frequency_taper: 1,
+            word_frequency_minimum: 2,
+            word_similarity_threshold: 0.8,
+            word_similarity_taper: 0.05,
+            word_similarity_minimum: 0.3,
+            corridor_width: 1.0,
+            min_coverage: 0.9,
+            ..Default::default()
+        }
+    }
+
+    /// Subtitles: many short lines aligned almost monotonically, with frequently repeated lines
+    pub fn subtitles() -> Self {
+        Self {
+            anchor_threshold: 2,
+            max_cycles: 20,
+            word_frequency_threshold: 3,
+            word_frequency_taper: 1,
+            word_frequency_minimum: 2,
+            word_similarity_threshold: 0.7,
+            word_similarity_taper: 0.05,
+            word_similarity_minimum: 0.4,
+            corridor_width: 0.5,
+            positional_disambiguation: true,
+            min_coverage: 0.9,
+            ..Default::default()
+        }
+    }
+
+    /// Legal and administrative texts: close translations with formulaic, repeated wording where
+    /// precision matters more than recall
+    pub fn legal() -> Self {
+        Self {
+            anchor_threshold: 4,
+            max_cycles: 30,
+            word_frequency_threshold: 5,
+            word_frequency_taper: 0,
+            word_frequency_minimum: 0,
+            word_similarity_threshold: 0.85,
+            word_similarity_taper: 0.03,
+            word_similarity_minimum: 0.5,
+            corridor_width: 1.0,
+            positional_disambiguation: true,
+            min_coverage: 0.98,
+            ..Default::default()
+        }
+    }
+
+    /// OCR'd scans: damaged words lower frequencies and similarities, and segmentation errors cause drift
+    pub fn noisy_ocr() -> Self {
+        Self {
+            anchor_threshold: 3,
+            max_cycles: 25,
+            word_frequency_threshold: 3,
+            word_frequency_taper: 1,
+            word_frequency_minimum: 2,
+            word_similarity_threshold: 0.7,
+            word_similarity_taper: 0.05,
+            word_similarity_minimum: 0.3,
+            corridor_width: 1.5,
+            min_coverage: 0.85,
+            ..Default::default()
+        }
+    }
+}
+
+impl<Word: PartialEq> Config<Word> {
+    /// Revisions or editions of the same text: identical words are associated outright rather than
+    /// through their co-occurrences, so the alignment needs no bootstrap. Use `Output::revisions` to
+    /// classify the sentences as matched, modified or moved
+    pub fn revision() -> Self {
+        Self {
+            anchor_threshold: 2,
+            max_cycles: 10,
+            word_frequency_threshold: 2,
+            word_frequency_taper: 0,
+            word_frequency_minimum: 2,
+            word_similarity_threshold: 1.0,
+            word_similarity_taper: 0.0,
+            word_similarity_minimum: 1.0,
+            corridor_width: 1.0,
+            min_coverage: 0.98,
+            association_mapper: mappers::identity(),
+            ..Default::default()
+        }
+    }
+}
@@ -0,0 +1,82 @@
+use super::MemoryUsage;
+
+/// Progress of an alignment, reported at the end of each cycle
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Progress {
+    /// Number of cycles completed
+    pub cycle: usize,
+    /// Maximum number of cycles which will be performed
+    pub max_cycles: usize,
+    /// Number of cells in the corridor of alignable sentences searched by the cycle
+    pub corridor: usize,
+    /// Number of word associations processed by the cycle
+    pub associations: usize,
+    /// Approximate memory held during the cycle
+    pub memory: MemoryUsage,
+    /// Coverage obtained after the cycle
+    pub coverage: f32,
+    /// Coverage at which the alignment is considered finished
+    pub min_coverage: f32,
+}
+
+/// Receives progress updates while an alignment is running
+pub trait ProgressReporter {
+    /// Called before the first cycle
+    fn start(&self, _max_cycles: usize) {}
+
+    /// Called at the end of every cycle
+    fn cycle(&self, progress: &Progress);
+
+    /// Called once the alignment is finished, with the progress of the final cycle
+    fn finish(&self, _progress: &Progress) {}
+}
+
+impl<F> ProgressReporter for F
+where
+    F: Fn(&Progress),
+{
+    fn cycle(&self, progress: &Progress) {
+        self(progress)
+    }
+}
+
+/// Reports progress on an `indicatif` progress bar, advancing by one position per cycle
+#[cfg(feature = "indicatif")]
+pub struct IndicatifReporter(pub indicatif::ProgressBar);
+
+#[cfg(feature = "indicatif")]
+impl Default for IndicatifReporter {
+    fn default() -> Self {
+        let bar = indicatif::ProgressBar::new(0);
+        bar.set_style(
+            indicatif::ProgressStyle::with_template(
+                "{elapsed_precise} [{bar:40}] cycle {pos}/{len} {msg}",
+            )
+            .unwrap_or_else(|_| indicatif::ProgressStyle::default_bar()),
+        );
+
+        Self(bar)
+    }
+}
+
+#[cfg(feature = "indicatif")]
+impl ProgressReporter for IndicatifReporter {
+    fn start(&self, max_cycles: usize) {
+        self.0.set_length(max_cycles as u64);
+    }
+
+    fn cycle(&self, progress: &Progress) {
+        self.0.set_position(progress.cycle as u64);
+        self.0.set_message(format!(
+            "coverage {:.1}% of {:.1}%, {} associations",
+            progress.coverage * 100.0,
+            progress.min_coverage * 100.0,
+            progress.associations,
+        ));
+    }
+
+    fn finish(&self, progress: &Progress) {
+        self.0.set_length(progress.cycle as u64);
+        self.0.finish();
+    }
+}
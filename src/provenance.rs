@@ -0,0 +1,62 @@
+use super::hash::HashMap;
+use super::{Coordinates, Output, X, Y};
+
+/// A word association which incremented the score of an aligned pair
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Evidence {
+    /// Offset of the associated word within sentence `a`
+    pub a_word: usize,
+    /// Offset of the associated word within sentence `b`
+    pub b_word: usize,
+    /// Similarity of the association
+    pub similarity: f32,
+    /// Cycle in which the association incremented the pair
+    pub cycle: usize,
+}
+
+/// Evidence gathered for each cell of the SAT while aligning
+#[derive(Debug, Default)]
+pub(crate) struct Provenance {
+    pub(crate) cycle: usize,
+    evidence: HashMap<Coordinates, Vec<Evidence>>,
+}
+
+impl Provenance {
+    pub(crate) fn add(
+        &mut self,
+        coordinates: Coordinates,
+        a_word: usize,
+        b_word: usize,
+        similarity: f32,
+    ) {
+        self.evidence
+            .entry(coordinates)
+            .or_default()
+            .push(Evidence {
+                a_word,
+                b_word,
+                similarity,
+                cycle: self.cycle,
+            });
+    }
+
+    /// Returns the evidence of the cells for which `keep` returns true
+    pub(crate) fn retain(
+        mut self,
+        keep: impl Fn(Coordinates) -> bool,
+    ) -> HashMap<Coordinates, Vec<Evidence>> {
+        self.evidence.retain(|&coordinates, _| keep(coordinates));
+        self.evidence
+    }
+}
+
+impl<'a, T, U> Output<'a, T, U> {
+    /// Returns the word associations which incremented the aligned pair `(a, b)`, in the order they were
+    /// applied. Always empty unless the texts were aligned with `Config::provenance` enabled
+    pub fn provenance(&self, a: usize, b: usize) -> &[Evidence] {
+        self.provenance
+            .get(&Coordinates(X(b), Y(a)))
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+}
@@ -0,0 +1,96 @@
+use super::{Coordinates, Output, SentenceAlignmentTable};
+
+/// Cleanup performed once the cycles have finished, flagging the anchors which lie far off the diagonal
+/// fitted to their neighboring anchors. Such isolated anchors almost always result from coincidental
+/// matches of rare words
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OutlierPruning {
+    /// Number of neighboring anchors on each side of an anchor to which the local diagonal is fitted.
+    /// Defaults to `4`
+    pub neighbors: usize,
+    /// Distance in sentences of text `b` from the local diagonal beyond which an anchor is an outlier.
+    /// Defaults to `10.0`
+    pub max_deviation: f32,
+    /// Removes the outliers from the alignment, rather than only reporting them through
+    /// `Output::outliers`. Defaults to `true`
+    pub remove: bool,
+}
+
+impl Default for OutlierPruning {
+    fn default() -> Self {
+        Self {
+            neighbors: 4,
+            max_deviation: 10.0,
+            remove: true,
+        }
+    }
+}
+
+impl OutlierPruning {
+    /// Returns the unlocked anchors of the SAT lying off their local diagonal, removing them if configured to
+    pub(crate) fn prune(&self, sat: &mut SentenceAlignmentTable) -> Vec<Coordinates> {
+        let mut anchors = sat.anchors().collect::<Vec<_>>();
+        anchors.sort_unstable_by_key(|&Coordinates(x, y)| (y, x));
+
+        let outliers = anchors
+            .iter()
+            .enumerate()
+            .filter(|&(i, &Coordinates(x, y))| {
+                let neighbors = anchors[i.saturating_sub(self.neighbors)..i]
+                    .iter()
+                    .chain(anchors.iter().skip(i + 1).take(self.neighbors))
+                    .map(|&Coordinates(x, y)| (y.0 as f32, x.0 as f32))
+                    .collect::<Vec<_>>();
+
+                fit(&neighbors).is_some_and(|(slope, intercept)| {
+                    (x.0 as f32 - (slope * y.0 as f32 + intercept)).abs() > self.max_deviation
+                })
+            })
+            .map(|(_, &coordinates)| coordinates)
+            .filter(|&coordinates| !sat.is_locked(coordinates))
+            .collect::<Vec<_>>();
+
+        if self.remove {
+            for &coordinates in &outliers {
+                sat.remove(coordinates);
+            }
+        }
+
+        outliers
+    }
+}
+
+/// Least squares fit of a line to `points`, returning its slope and intercept. Falls back to a slope of
+/// `1` through the mean point if the points do not determine a slope
+fn fit(points: &[(f32, f32)]) -> Option<(f32, f32)> {
+    if points.len() < 2 {
+        return None;
+    }
+
+    let n = points.len() as f32;
+    let mean_a = points.iter().map(|(a, _)| a).sum::<f32>() / n;
+    let mean_b = points.iter().map(|(_, b)| b).sum::<f32>() / n;
+    let covariance = points
+        .iter()
+        .map(|(a, b)| (a - mean_a) * (b - mean_b))
+        .sum::<f32>();
+    let variance = points
+        .iter()
+        .map(|(a, _)| (a - mean_a).powi(2))
+        .sum::<f32>();
+
+    let slope = if variance > 0.0 {
+        covariance / variance
+    } else {
+        1.0
+    };
+    Some((slope, mean_b - slope * mean_a))
+}
+
+impl<'a, T, U> Output<'a, T, U> {
+    /// Returns the pairs of sentence indices `(a, b)` of the anchors flagged by the `OutlierPruning`,
+    /// ordered by `a`. Always empty unless the texts were aligned with an `OutlierPruning`
+    pub fn outliers(&self) -> &[(usize, usize)] {
+        &self.outliers
+    }
+}
@@ -0,0 +1,107 @@
+use std::collections::HashMap;
+
+use super::sentence_alignment_table::{Score, SentenceAlignmentTable};
+use super::{Coordinates, Sentence};
+
+/// External quality estimation model scoring a candidate pair of sentences, e.g. an inexpensive
+/// cross-lingual classifier. The sentences are those of the texts being aligned, so that the model may
+/// also consult their `span` in the original documents
+pub trait QualityEstimator<Word: PartialEq> {
+    /// Returns the estimated quality of the pair of the sentences `a` and `b`, from `0` for unrelated
+    /// sentences to `1` for translations of one another
+    fn estimate(&self, a: &dyn Sentence<Word>, b: &dyn Sentence<Word>) -> f32;
+}
+
+impl<Word, F> QualityEstimator<Word> for F
+where
+    Word: PartialEq,
+    F: Fn(&dyn Sentence<Word>, &dyn Sentence<Word>) -> f32,
+{
+    fn estimate(&self, a: &dyn Sentence<Word>, b: &dyn Sentence<Word>) -> f32 {
+        self(a, b)
+    }
+}
+
+/// Quality estimation consulted once for each cell whose score comes within the `margin` of the anchor
+/// threshold, the estimate being blended into the score deciding whether the cell is an anchor. A good
+/// estimate may complete an anchor before the word associations do, and a poor one holds the cell back
+/// until further evidence outweighs it
+pub struct QualityEstimation<Word> {
+    pub estimator: Box<dyn QualityEstimator<Word>>,
+    /// Number of points below the anchor threshold from which a cell is estimated. Defaults to `1`
+    pub margin: usize,
+    /// Number of points by which an estimate of `1` raises the score of a cell, and an estimate of `0`
+    /// lowers it. Defaults to `1.0`
+    pub weight: f32,
+}
+
+impl<Word: PartialEq> QualityEstimation<Word> {
+    pub fn new(estimator: impl QualityEstimator<Word> + 'static) -> Self {
+        Self {
+            estimator: Box::new(estimator),
+            margin: 1,
+            weight: 1.0,
+        }
+    }
+
+    /// Returns the points blended into the score of the cell of the sentences `a` and `b`
+    fn adjustment(&self, a: &dyn Sentence<Word>, b: &dyn Sentence<Word>) -> isize {
+        let estimate = self.estimator.estimate(a, b).clamp(0.0, 1.0);
+        (self.weight * (2.0 * estimate - 1.0)).round() as isize
+    }
+
+    /// Blends the estimates into the scores of the `cells` which were just incremented, estimating the
+    /// cells near the anchor threshold with the sentences returned by `sentences`
+    pub(crate) fn apply<'w>(
+        &self,
+        sat: &mut SentenceAlignmentTable,
+        estimates: &mut Estimates,
+        cells: &[Coordinates],
+        sentences: impl Fn(Coordinates) -> (&'w dyn Sentence<Word>, &'w dyn Sentence<Word>),
+    ) where
+        Word: 'w,
+    {
+        let threshold = sat.anchor_threshold().0;
+        for &coordinates in cells {
+            if sat.is_locked(coordinates) {
+                continue;
+            }
+            let score = sat.score(coordinates).0;
+            let raw = score
+                + estimates
+                    .get(&coordinates)
+                    .map_or(0, |estimate| estimate.held);
+            if raw + self.margin < threshold {
+                continue;
+            }
+
+            let estimate = estimates.entry(coordinates).or_insert_with(|| {
+                let (a, b) = sentences(coordinates);
+                Estimate {
+                    adjustment: self.adjustment(a, b),
+                    held: 0,
+                }
+            });
+            if raw as isize + estimate.adjustment >= threshold as isize {
+                if score < threshold {
+                    sat.add(coordinates, Score(raw.max(threshold) - score));
+                    estimate.held = 0;
+                }
+            } else if score >= threshold {
+                // The cell is kept just below the threshold, remembering the evidence held back
+                let excess = score + 1 - threshold;
+                sat.subtract(coordinates, Score(excess));
+                estimate.held += excess;
+            }
+        }
+    }
+}
+
+/// Points blended into the score of each estimated cell
+pub(crate) type Estimates = HashMap<Coordinates, Estimate>;
+
+pub(crate) struct Estimate {
+    adjustment: isize,
+    /// Points of evidence withheld from the SAT while the cell was held back
+    held: usize,
+}
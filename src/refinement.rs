@@ -0,0 +1,132 @@
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+use super::{Coordinates, Sentence, SentenceAlignmentTable, WordSentenceIndex, X, Y};
+
+/// Refinement phase performed once the cycles have finished. Word similarities are recomputed from the
+/// co-occurrences of words in the confident anchors alone, and this sharper lexicon decides the cells
+/// whose score lies near the anchor threshold
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Refinement {
+    /// Cells scored at least this far above the anchor threshold are confident anchors, from which the
+    /// lexicon is learned, while the other cells scored within this distance of the threshold are
+    /// re-scored. Defaults to `1`
+    pub margin: usize,
+    /// Number of confident anchors in which a pair of words must co-occur to enter the lexicon.
+    /// Defaults to `2`
+    pub min_frequency: usize,
+    /// Similarity of a pair of words over the confident anchors required to enter the lexicon.
+    /// Defaults to `0.5`
+    pub similarity_threshold: f32,
+    /// Fraction of the words of the shorter sentence of a re-scored cell which must have a counterpart
+    /// in the lexicon for it to be an anchor. Cells below this fraction are discarded. Defaults to `0.3`
+    pub min_support: f32,
+}
+
+impl Default for Refinement {
+    fn default() -> Self {
+        Self {
+            margin: 1,
+            min_frequency: 2,
+            similarity_threshold: 0.5,
+            min_support: 0.3,
+        }
+    }
+}
+
+impl Refinement {
+    /// Re-scores the cells near the anchor threshold, returning the cells which became anchors
+    pub(crate) fn refine<'a, Word, T, U>(
+        &self,
+        sat: &mut SentenceAlignmentTable,
+        a: &'a [T],
+        b: &'a [U],
+        a_word_sentence_index: &WordSentenceIndex<'a, Word, Y>,
+        b_word_sentence_index: &WordSentenceIndex<'a, Word, X>,
+    ) -> Vec<Coordinates>
+    where
+        Word: Eq + Hash,
+        T: Sentence<Word>,
+        U: Sentence<Word>,
+    {
+        let threshold = sat.anchor_threshold().0;
+        let a_words = |Y(y): Y| {
+            a[y].words()
+                .iter()
+                .map(|word| a_word_sentence_index.canonical(word))
+                .collect::<HashSet<_>>()
+        };
+        let b_words = |X(x): X| {
+            b[x].words()
+                .iter()
+                .map(|word| b_word_sentence_index.canonical(word))
+                .collect::<HashSet<_>>()
+        };
+
+        let confident = sat
+            .cells()
+            .filter(|(_, score)| score.0 >= threshold + self.margin)
+            .map(|(coordinates, _)| coordinates)
+            .collect::<Vec<_>>();
+        let mut a_frequencies: HashMap<&Word, usize> = HashMap::new();
+        let mut b_frequencies: HashMap<&Word, usize> = HashMap::new();
+        let mut joint_frequencies: HashMap<(&Word, &Word), usize> = HashMap::new();
+        for &Coordinates(x, y) in &confident {
+            let (a_words, b_words) = (a_words(y), b_words(x));
+            for &a_word in &a_words {
+                *a_frequencies.entry(a_word).or_default() += 1;
+                for &b_word in &b_words {
+                    *joint_frequencies.entry((a_word, b_word)).or_default() += 1;
+                }
+            }
+            for &b_word in &b_words {
+                *b_frequencies.entry(b_word).or_default() += 1;
+            }
+        }
+
+        let lexicon = joint_frequencies
+            .into_iter()
+            .filter(|&((a_word, b_word), joint)| {
+                joint >= self.min_frequency
+                    && (2 * joint) as f32 / (a_frequencies[a_word] + b_frequencies[b_word]) as f32
+                        >= self.similarity_threshold
+            })
+            .map(|(pair, _)| pair)
+            .collect::<HashSet<_>>();
+
+        let borderline = sat
+            .cells()
+            .filter(|(_, score)| {
+                score.0 + self.margin >= threshold && score.0 < threshold + self.margin
+            })
+            .map(|(coordinates, _)| coordinates)
+            .collect::<Vec<_>>();
+        let mut promoted = vec![];
+        for coordinates @ Coordinates(x, y) in borderline {
+            let (a_words, b_words) = (a_words(y), b_words(x));
+            let supported = a_words
+                .iter()
+                .filter(|&&a_word| {
+                    b_words
+                        .iter()
+                        .any(|&b_word| lexicon.contains(&(a_word, b_word)))
+                })
+                .count();
+            let support = supported as f32 / a_words.len().min(b_words.len()).max(1) as f32;
+
+            let crosses_confident = confident.iter().any(|&Coordinates(other_x, other_y)| {
+                (other_x > x && other_y < y) || (other_x < x && other_y > y)
+            });
+            if support >= self.min_support && !crosses_confident {
+                if !sat.is_anchor(coordinates) {
+                    sat.anchor(coordinates);
+                    promoted.push(coordinates);
+                }
+            } else {
+                sat.remove(coordinates);
+            }
+        }
+
+        promoted
+    }
+}
@@ -0,0 +1,20 @@
+/// A cell of the SAT which will become an anchor when the association proposing it is applied
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CandidateAnchor<'a, Word> {
+    /// Index of the sentence in text `a`
+    pub a: usize,
+    /// Index of the sentence in text `b`
+    pub b: usize,
+    /// Word of text `a` whose association proposed the anchor
+    pub a_word: &'a Word,
+    /// Word of text `b` whose association proposed the anchor
+    pub b_word: &'a Word,
+    /// Similarity of the proposing association
+    pub similarity: f32,
+    /// Score of the cell once the association is applied
+    pub score: usize,
+}
+
+/// Hook invoked with the candidate anchors of each association before they are committed. Candidates
+/// removed from the batch are vetoed, and the remaining candidates are committed in the order given
+pub type Reranker<Word> = Box<dyn for<'a> Fn(&mut Vec<CandidateAnchor<'a, Word>>)>;
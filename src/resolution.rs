@@ -0,0 +1,126 @@
+use super::hash::HashMap;
+use super::Coordinates;
+
+/// Returns the subset of `proposals` of maximum total weight in which no two cells cross one another,
+/// ordered by position
+pub(crate) fn resolve(proposals: &HashMap<Coordinates, u64>) -> Vec<Coordinates> {
+    let mut cells = proposals.iter().map(|(&c, &w)| (c, w)).collect::<Vec<_>>();
+    cells.sort_unstable_by_key(|(Coordinates(x, y), _)| (*x, *y));
+
+    let mut ys = cells.iter().map(|(c, _)| c.y()).collect::<Vec<_>>();
+    ys.sort_unstable();
+    ys.dedup();
+
+    // Maximum weight chain ending at each cell, found by a Fenwick tree over the ranks of the y positions
+    // holding the best chain ending at or below each rank
+    let mut tree: Vec<Option<(u64, usize)>> = vec![None; ys.len() + 1];
+    let mut previous = vec![None; cells.len()];
+    let mut best = vec![0; cells.len()];
+    for (i, &(coordinates, weight)) in cells.iter().enumerate() {
+        let rank = ys.binary_search(&coordinates.y()).unwrap() + 1;
+
+        let mut prefix: Option<(u64, usize)> = None;
+        let mut r = rank;
+        while r > 0 {
+            if tree[r].is_some_and(|(w, _)| prefix.is_none_or(|(p, _)| w > p)) {
+                prefix = tree[r];
+            }
+            r &= r - 1;
+        }
+
+        previous[i] = prefix.map(|(_, j)| j);
+        best[i] = prefix.map_or(0, |(w, _)| w) + weight;
+
+        let mut r = rank;
+        while r < tree.len() {
+            if tree[r].is_none_or(|(w, _)| best[i] > w) {
+                tree[r] = Some((best[i], i));
+            }
+            r += r & r.wrapping_neg();
+        }
+    }
+
+    let mut chain = vec![];
+    let mut current = best
+        .iter()
+        .enumerate()
+        .max_by_key(|&(_, weight)| weight)
+        .map(|(i, _)| i);
+    while let Some(i) = current {
+        chain.push(cells[i].0);
+        current = previous[i];
+    }
+
+    chain.reverse();
+    chain
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use crate::{AlignedPair, Config, Event, Rejection};
+
+    /// Returns the rejections of the association of "k" with "K", whose match of the sentences `(1, 1)`
+    /// crosses the evidence seeded at `(0, 3)`, and the pairs aligned
+    fn rejections(global_resolution: bool) -> (Vec<Rejection>, Vec<(usize, usize)>) {
+        let a = [
+            vec!["a0"],
+            vec!["k", "a1"],
+            vec!["a2"],
+            vec!["a3"],
+            vec!["k", "a4"],
+            vec!["a5"],
+        ];
+        let b = [
+            vec!["b0"],
+            vec!["K", "b1"],
+            vec!["b2"],
+            vec!["b3"],
+            vec!["K", "b4"],
+            vec!["b5"],
+        ];
+        let a = a.iter().map(Vec::as_slice).collect::<Vec<_>>();
+        let b = b.iter().map(Vec::as_slice).collect::<Vec<_>>();
+        let rejected = Arc::new(Mutex::new(vec![]));
+        let observed = rejected.clone();
+        let output = Config {
+            word_frequency_threshold: 2,
+            global_resolution,
+            observer: Some(Box::new(move |event: Event<'_, &str>| {
+                if let Event::AssociationRejected {
+                    a_word: &"k",
+                    reason,
+                    ..
+                } = event
+                {
+                    observed.lock().unwrap().push(reason);
+                }
+            })),
+            ..Default::default()
+        }
+        .align_from(
+            &a,
+            &b,
+            [AlignedPair {
+                a: 0,
+                b: 3,
+                score: 1,
+            }],
+        );
+
+        let rejected = rejected.lock().unwrap().clone();
+        (
+            rejected,
+            output.pairs().map(|pair| pair.indices()).collect(),
+        )
+    }
+
+    #[test]
+    fn crossing_associations_are_rejected_as_when_greedy() {
+        let greedy = rejections(false);
+        let global = rejections(true);
+        assert!(greedy.0.contains(&Rejection::Crossover { a: 1, b: 1 }));
+        assert_eq!(global, greedy);
+    }
+}
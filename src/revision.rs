@@ -0,0 +1,73 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use super::{Output, Sentence, X, Y};
+
+/// Relation between a sentence of a text and its counterpart in a revision of the text
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Change {
+    /// Aligned, with identical words
+    Matched,
+    /// Aligned, with different words
+    Modified,
+    /// Identical words, at a position the alignment does not reach, e.g. a paragraph moved elsewhere
+    Moved,
+}
+
+/// A pair of sentence indices `(a, b)` of two revisions of a text, and how the sentence changed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Revision {
+    pub a: usize,
+    pub b: usize,
+    pub change: Change,
+}
+
+impl<'a, T, U> Output<'a, T, U> {
+    /// Classifies the aligned pairs as matched or modified, and pairs the unaligned sentences of both
+    /// texts with identical words as moved. Pairs are ordered by `a`. Sentences absent from the result
+    /// were deleted from text `a` or inserted in text `b`
+    pub fn revisions<Word>(&self) -> Vec<Revision>
+    where
+        Word: Eq + Hash,
+        T: Sentence<Word>,
+        U: Sentence<Word>,
+    {
+        let mut revisions = self
+            .pairs()
+            .map(|pair| Revision {
+                a: pair.a,
+                b: pair.b,
+                change: if self.a[pair.a].words() == self.b[pair.b].words() {
+                    Change::Matched
+                } else {
+                    Change::Modified
+                },
+            })
+            .collect::<Vec<_>>();
+
+        let mut unaligned: HashMap<&[Word], Vec<usize>> = HashMap::new();
+        for j in (0..self.b.len()).rev() {
+            if !self.b_alignment_map().contains_key(&X(j)) {
+                unaligned.entry(self.b[j].words()).or_default().push(j);
+            }
+        }
+        for i in 0..self.a.len() {
+            if self.a_alignments.contains_key(&Y(i)) {
+                continue;
+            }
+            if let Some(j) = unaligned
+                .get_mut(self.a[i].words())
+                .and_then(|candidates| candidates.pop())
+            {
+                revisions.push(Revision {
+                    a: i,
+                    b: j,
+                    change: Change::Moved,
+                });
+            }
+        }
+        revisions.sort_unstable_by_key(|revision| (revision.a, revision.b));
+
+        revisions
+    }
+}
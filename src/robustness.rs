@@ -0,0 +1,189 @@
+//! Measurement of how the quality of an alignment degrades as one of the texts is perturbed
+
+use std::hash::Hash;
+
+use super::synthetic::Rng;
+use super::tune::{ConfigFactory, Evaluation};
+use super::{Config, Sentence};
+
+/// Controlled distortion applied to text `b`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Perturbation {
+    /// Each sentence is dropped with probability of the level, keeping at least one sentence drawn at
+    /// random
+    Deletion,
+    /// Each sentence is swapped with the next with probability of the level
+    Swap,
+    /// Each word is replaced by a word drawn at random from the text with probability of the level
+    Noise,
+}
+
+/// A sentence of a perturbed text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PerturbedSentence<Word>(pub Vec<Word>);
+
+impl<Word: PartialEq> Sentence<Word> for PerturbedSentence<Word> {
+    fn words(&self) -> &[Word] {
+        &self.0
+    }
+}
+
+/// Quality of the alignment at each level of one perturbation
+#[derive(Debug, Clone, PartialEq)]
+pub struct Curve {
+    pub perturbation: Perturbation,
+    /// Evaluation at each level, in the order of `Harness::levels`
+    pub points: Vec<(f32, Evaluation)>,
+}
+
+pub struct Harness<Word> {
+    /// Provides the configuration with which each perturbed pair is aligned. Defaults to `Config::default`
+    pub config: ConfigFactory<Word>,
+    /// Defaults to every perturbation
+    pub perturbations: Vec<Perturbation>,
+    /// Probabilities with which each perturbation is applied. Defaults to `[0.0, 0.05, 0.1, 0.2, 0.3]`
+    pub levels: Vec<f32>,
+    /// Seed of the pseudo-random perturbations. Defaults to `1`
+    pub seed: u64,
+}
+
+impl<Word> Default for Harness<Word> {
+    fn default() -> Self {
+        Self {
+            // The closure avoids requiring `Word: 'static`, which boxing `Config::default` would
+            #[allow(clippy::redundant_closure)]
+            config: Box::new(|| Config::default()),
+            perturbations: vec![
+                Perturbation::Deletion,
+                Perturbation::Swap,
+                Perturbation::Noise,
+            ],
+            levels: vec![0.0, 0.05, 0.1, 0.2, 0.3],
+            seed: 1,
+        }
+    }
+}
+
+impl<Word: PartialEq + Clone> Harness<Word> {
+    /// Perturbs `b` and realigns it with `a` at every level of every perturbation, evaluating each
+    /// alignment against the `gold` pairs of sentence indices `(a, b)` carried through the perturbation
+    pub fn run<T, U>(&self, a: &[T], b: &[U], gold: &[(usize, usize)]) -> Vec<Curve>
+    where
+        Word: Eq + Hash,
+        T: Sentence<Word>,
+        U: Sentence<Word>,
+    {
+        self.perturbations
+            .iter()
+            .map(|&perturbation| Curve {
+                perturbation,
+                points: self
+                    .levels
+                    .iter()
+                    .map(|&level| {
+                        let (b, gold) = self.perturb(b, gold, perturbation, level);
+                        let output = (self.config)().align(a, &b);
+                        (
+                            level,
+                            Evaluation::new(output.pairs().map(|pair| pair.indices()), &gold),
+                        )
+                    })
+                    .collect(),
+            })
+            .collect()
+    }
+
+    /// Applies `perturbation` to `b` with probability `level`, returning the perturbed text and the
+    /// `gold` pairs of sentence indices `(a, b)` remapped onto it
+    pub fn perturb<U: Sentence<Word>>(
+        &self,
+        b: &[U],
+        gold: &[(usize, usize)],
+        perturbation: Perturbation,
+        level: f32,
+    ) -> (Vec<PerturbedSentence<Word>>, Vec<(usize, usize)>) {
+        let mut rng = Rng(self.seed.max(1));
+        let mut sentences = b
+            .iter()
+            .map(|sentence| PerturbedSentence(sentence.words().to_vec()))
+            .collect::<Vec<_>>();
+        let mut positions = (0..b.len()).map(Some).collect::<Vec<_>>();
+
+        match perturbation {
+            Perturbation::Deletion => {
+                let mut kept = (0..b.len()).map(|_| !rng.chance(level)).collect::<Vec<_>>();
+                // The texts to align must not be empty
+                if !b.is_empty() && !kept.contains(&true) {
+                    kept[rng.range(0..b.len())] = true;
+                }
+                let mut position = 0;
+                for (j, &kept) in kept.iter().enumerate() {
+                    positions[j] = kept.then_some(position);
+                    position += usize::from(kept);
+                }
+                let mut kept = kept.into_iter();
+                sentences.retain(|_| kept.next().unwrap_or(true));
+            }
+            Perturbation::Swap => {
+                let mut j = 0;
+                while j + 1 < sentences.len() {
+                    if rng.chance(level) {
+                        sentences.swap(j, j + 1);
+                        positions.swap(j, j + 1);
+                        j += 2;
+                    } else {
+                        j += 1;
+                    }
+                }
+                let mut moved = vec![None; b.len()];
+                for (position, original) in positions.iter().enumerate() {
+                    if let Some(original) = *original {
+                        moved[original] = Some(position);
+                    }
+                }
+                positions = moved;
+            }
+            Perturbation::Noise => {
+                let words = b
+                    .iter()
+                    .flat_map(|sentence| sentence.words())
+                    .collect::<Vec<_>>();
+                for word in sentences.iter_mut().flat_map(|sentence| &mut sentence.0) {
+                    if rng.chance(level) {
+                        *word = words[rng.range(0..words.len())].clone();
+                    }
+                }
+            }
+        }
+
+        let mut gold = gold
+            .iter()
+            .filter_map(|&(i, j)| Some((i, positions.get(j).copied().flatten()?)))
+            .collect::<Vec<_>>();
+        gold.sort_unstable();
+
+        (sentences, gold)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deletion_keeps_a_sentence() {
+        let text = [vec!["one", "two"], vec!["three"], vec!["four", "five"]];
+        let text = text.iter().map(Vec::as_slice).collect::<Vec<_>>();
+        let gold = [(0, 0), (1, 1), (2, 2)];
+        let harness = Harness {
+            perturbations: vec![Perturbation::Deletion],
+            levels: vec![1.0],
+            ..Default::default()
+        };
+
+        let (b, remapped) = harness.perturb(&text, &gold, Perturbation::Deletion, 1.0);
+        assert_eq!(b.len(), 1);
+        assert_eq!(remapped.len(), 1);
+        assert_eq!(harness.run(&text, &text, &gold)[0].points.len(), 1);
+    }
+}
@@ -1,10 +1,12 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashSet};
+use std::mem::size_of;
 use std::ops::{AddAssign, Bound};
+use std::sync::Arc;
 
 use super::{Coordinates, X, Y};
 
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
-pub struct Score(usize);
+pub struct Score(pub(crate) usize);
 
 impl AddAssign for Score {
     fn add_assign(&mut self, other: Self) {
@@ -22,11 +24,15 @@ impl Score {
     pub const ZERO: Self = Self(0);
 }
 
-#[derive(Debug)]
+/// Rows are shared between clones of the table until written, so cloning is proportional to the number
+/// of rows rather than the number of cells
+#[derive(Debug, Clone)]
 pub struct SentenceAlignmentTable {
     anchor_threshold: Score,
-    map: BTreeMap<X, BTreeMap<Y, Score>>,
+    map: BTreeMap<X, Arc<BTreeMap<Y, Score>>>,
     end: Coordinates,
+    forbidden: HashSet<Coordinates>,
+    locked: HashSet<Coordinates>,
 }
 
 impl SentenceAlignmentTable {
@@ -35,9 +41,53 @@ impl SentenceAlignmentTable {
             anchor_threshold,
             map: Default::default(),
             end: Coordinates(X(b.len() - 1), Y(a.len() - 1)),
+            forbidden: Default::default(),
+            locked: Default::default(),
         }
     }
 
+    /// Sets the anchor threshold. Locked anchors remain anchors whatever their score
+    pub(crate) fn set_anchor_threshold(&mut self, anchor_threshold: Score) {
+        self.anchor_threshold = anchor_threshold;
+    }
+
+    /// Raises the locked anchors to the anchor threshold, for a table seeding a run which weighs their
+    /// scores against it
+    pub(crate) fn raise_locked(&mut self) {
+        for coordinates in self.locked.clone() {
+            self.anchor(coordinates);
+        }
+    }
+
+    /// Extends the table to texts of `a_len` and `b_len` sentences
+    pub(crate) fn resize(&mut self, a_len: usize, b_len: usize) {
+        self.end = Coordinates(X(b_len - 1), Y(a_len - 1));
+    }
+
+    /// Returns the approximate memory held by the table, in bytes
+    pub(crate) fn memory(&self) -> usize {
+        let row =
+            size_of::<X>() + size_of::<Arc<BTreeMap<Y, Score>>>() + size_of::<BTreeMap<Y, Score>>();
+        let cells = self.map.values().map(|ys| ys.len()).sum::<usize>();
+
+        self.map.len() * row
+            + cells * (size_of::<Y>() + size_of::<Score>())
+            + (self.forbidden.len() + self.locked.len()) * size_of::<Coordinates>()
+    }
+
+    pub(crate) fn anchor_threshold(&self) -> Score {
+        self.anchor_threshold
+    }
+
+    /// Returns all cells with a non-zero score
+    pub(crate) fn cells(&self) -> impl Iterator<Item = (Coordinates, Score)> + '_ {
+        self.map.iter().flat_map(|(&x, ys)| {
+            ys.iter()
+                .filter(|(_, &score)| score > Score::ZERO)
+                .map(move |(&y, &score)| (Coordinates(x, y), score))
+        })
+    }
+
     pub(crate) fn score(&self, Coordinates(x, y): Coordinates) -> Score {
         self.map
             .get(&x)
@@ -53,7 +103,8 @@ impl SentenceAlignmentTable {
                 .find_map(|(&x, ys)| {
                     ys.range((Bound::Excluded(y), Bound::Included(self.end.y())))
                         .find_map(|(&y, &score)| {
-                            (score >= self.anchor_threshold).then_some(Coordinates(x, y))
+                            self.is_anchor_score(Coordinates(x, y), score)
+                                .then_some(Coordinates(x, y))
                         })
                 })
                 .unwrap_or(self.end)
@@ -62,6 +113,21 @@ impl SentenceAlignmentTable {
         }
     }
 
+    /// Returns the chain of anchors walked by `next_anchor`, from the origin to the end of the texts
+    pub(crate) fn chain(&self) -> Vec<Coordinates> {
+        let mut chain = vec![self.next_anchor(None)];
+        loop {
+            let start = chain[chain.len() - 1];
+            let end = self.next_anchor(Some(start));
+            if start == end {
+                break;
+            }
+            chain.push(end);
+        }
+
+        chain
+    }
+
     pub(crate) fn crossover(&self, Coordinates(x, y): Coordinates) -> bool {
         self.map
             .range((Bound::Excluded(x), Bound::Included(self.end.x())))
@@ -82,15 +148,190 @@ impl SentenceAlignmentTable {
                 .is_some()
     }
 
-    pub(crate) fn increment(&mut self, Coordinates(x, y): Coordinates) {
-        *self.map.entry(x).or_default().entry(y).or_default() += Score(1);
+    pub(crate) fn is_anchor(&self, coordinates: Coordinates) -> bool {
+        self.is_anchor_score(coordinates, self.score(coordinates))
+    }
+
+    fn is_anchor_score(&self, coordinates: Coordinates, score: Score) -> bool {
+        score >= self.anchor_threshold || self.is_locked(coordinates)
+    }
+
+    /// Returns true if incrementing the score at `coordinates` would make it an anchor
+    pub(crate) fn completes_anchor(&self, coordinates: Coordinates) -> bool {
+        self.score(coordinates).0 + 1 == self.anchor_threshold.0
+    }
+
+    pub(crate) fn increment(&mut self, coordinates: Coordinates) {
+        self.add(coordinates, Score(1));
+    }
+
+    pub(crate) fn add(&mut self, coordinates @ Coordinates(x, y): Coordinates, score: Score) {
+        if !self.is_forbidden(coordinates) {
+            *Arc::make_mut(self.map.entry(x).or_default())
+                .entry(y)
+                .or_default() += score;
+        }
+    }
+
+    /// Decreases the score at `coordinates` by `score`, removing the cell once its score reaches zero
+    pub(crate) fn subtract(&mut self, coordinates @ Coordinates(x, y): Coordinates, score: Score) {
+        if self.is_locked(coordinates) {
+            return;
+        }
+        if self.score(coordinates).0 <= score.0 {
+            self.remove(coordinates);
+        } else if let Some(ys) = self.map.get_mut(&x) {
+            if let Some(current) = Arc::make_mut(ys).get_mut(&y) {
+                current.0 -= score.0;
+            }
+        }
+    }
+
+    /// Raises the score at `coordinates` to the anchor threshold if it is not already an anchor
+    pub(crate) fn anchor(&mut self, coordinates @ Coordinates(x, y): Coordinates) {
+        if !self.is_forbidden(coordinates) {
+            let score = Arc::make_mut(self.map.entry(x).or_default())
+                .entry(y)
+                .or_default();
+            *score = (*score).max(self.anchor_threshold);
+        }
+    }
+
+    /// Clears the score at `coordinates` and prevents it from receiving any further evidence, unless it is
+    /// a locked anchor
+    pub(crate) fn forbid(&mut self, coordinates: Coordinates) {
+        if !self.is_locked(coordinates) {
+            self.remove(coordinates);
+            self.forbidden.insert(coordinates);
+        }
+    }
+
+    pub(crate) fn is_forbidden(&self, coordinates: Coordinates) -> bool {
+        self.forbidden.contains(&coordinates)
+    }
+
+    pub(crate) fn forbidden(&self) -> impl Iterator<Item = Coordinates> + '_ {
+        self.forbidden.iter().copied()
+    }
+
+    /// Makes `coordinates` an anchor which cannot be removed, clearing the cells crossing it. Cells
+    /// crossing a locked anchor are no longer candidates
+    pub(crate) fn lock(&mut self, coordinates: Coordinates) {
+        self.forbidden.remove(&coordinates);
+        self.remove_crossing(coordinates);
+        self.anchor(coordinates);
+        self.locked.insert(coordinates);
+    }
+
+    pub(crate) fn is_locked(&self, coordinates: Coordinates) -> bool {
+        self.locked.contains(&coordinates)
+    }
+
+    pub(crate) fn locked(&self) -> impl Iterator<Item = Coordinates> + '_ {
+        self.locked.iter().copied()
+    }
+
+    /// Returns true if `coordinates` crosses a locked anchor
+    pub(crate) fn crosses_locked(&self, Coordinates(x, y): Coordinates) -> bool {
+        self.locked.iter().any(|&Coordinates(locked_x, locked_y)| {
+            (locked_x > x && locked_y < y) || (locked_x < x && locked_y > y)
+        })
+    }
+
+    /// Clears the score at `coordinates`, unless it is a locked anchor
+    pub(crate) fn remove(&mut self, coordinates @ Coordinates(x, y): Coordinates) {
+        if self.is_locked(coordinates) {
+            return;
+        }
+        if let Some(ys) = self.map.get_mut(&x) {
+            if !ys.contains_key(&y) {
+                return;
+            }
+            Arc::make_mut(ys).remove(&y);
+            if ys.is_empty() {
+                self.map.remove(&x);
+            }
+        }
+    }
+
+    /// Clears the scores of all cells which are not anchors
+    pub(crate) fn retain_anchors(&mut self) {
+        let (anchor_threshold, locked) = (self.anchor_threshold, &self.locked);
+        for (&x, ys) in self.map.iter_mut() {
+            let anchor = |&y: &Y, &score: &Score| {
+                score >= anchor_threshold || locked.contains(&Coordinates(x, y))
+            };
+            if !ys.iter().all(|(y, score)| anchor(y, score)) {
+                Arc::make_mut(ys).retain(|y, score| anchor(y, score));
+            }
+        }
+        self.map.retain(|_, ys| !ys.is_empty());
+    }
+
+    /// Clears the scores of all cells crossing `coordinates`, except locked anchors
+    pub(crate) fn remove_crossing(&mut self, Coordinates(x, y): Coordinates) {
+        let locked = &self.locked;
+        for (&other_x, ys) in self.map.iter_mut() {
+            let crosses = |&other_y: &Y| {
+                ((other_x > x && other_y < y) || (other_x < x && other_y > y))
+                    && !locked.contains(&Coordinates(other_x, other_y))
+            };
+            if ys.keys().any(crosses) {
+                Arc::make_mut(ys).retain(|other_y, _| !crosses(other_y));
+            }
+        }
+        self.map.retain(|_, ys| !ys.is_empty());
+    }
+
+    /// Copies the cells, forbidden cells and locked anchors of `other`, a table of the sentences of the texts
+    /// starting at `offset`, keeping its anchors as anchors
+    pub(crate) fn merge(&mut self, other: &Self, Coordinates(X(x0), Y(y0)): Coordinates) {
+        let offset = |Coordinates(X(x), Y(y)): Coordinates| Coordinates(X(x + x0), Y(y + y0));
+        for &coordinates in &other.forbidden {
+            self.forbid(offset(coordinates));
+        }
+        for (coordinates, score) in other.cells() {
+            if other.is_anchor(coordinates) {
+                self.anchor(offset(coordinates));
+            } else {
+                self.add(offset(coordinates), score);
+            }
+        }
+        for &coordinates in &other.locked {
+            self.lock(offset(coordinates));
+        }
     }
 
     pub(crate) fn anchors(&self) -> impl Iterator<Item = Coordinates> + '_ {
         self.map.iter().flat_map(move |(&x, ys)| {
             ys.iter().filter_map(move |(&y, &score)| {
-                (score >= self.anchor_threshold).then_some(Coordinates(x, y))
+                self.is_anchor_score(Coordinates(x, y), score)
+                    .then_some(Coordinates(x, y))
             })
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locked_anchors_keep_their_score_above_the_threshold() {
+        let texts = [(); 3];
+        let mut sat = SentenceAlignmentTable::new(&texts, &texts, Score(2));
+        let locked = Coordinates(X(1), Y(1));
+        sat.lock(locked);
+        sat.add(Coordinates(X(2), Y(2)), Score(3));
+
+        sat.set_anchor_threshold(Score(5));
+        assert_eq!(sat.score(locked), Score(2));
+        assert!(sat.is_anchor(locked));
+        assert_eq!(sat.anchors().collect::<Vec<_>>(), [locked]);
+
+        sat.retain_anchors();
+        assert_eq!(sat.cells().count(), 1);
+        sat.raise_locked();
+        assert_eq!(sat.score(locked), Score(5));
+    }
+}
@@ -0,0 +1,134 @@
+use std::io::Read;
+use std::panic::{self, AssertUnwindSafe};
+use std::thread;
+
+use serde_json::{json, Value};
+use tiny_http::{Header, Method, Request, Response};
+
+use super::{Config, Sentence};
+
+type ServerConfigFactory = Box<dyn Fn() -> Config<String> + Sync>;
+
+/// Minimal HTTP service aligning texts posted as JSON.
+///
+/// `POST /align` with a body of the form `{"a": [...], "b": [...]}`, where each sentence is either a
+/// string, split on whitespace, or an array of words, responds with the aligned pairs of sentence
+/// indices `(a, b)` and the final coverage: `{"pairs": [[0, 0], ...], "coverage": 0.97}`
+pub struct Server {
+    /// Provides the configuration with which each request is aligned. Defaults to `Config::default`
+    pub config: ServerConfigFactory,
+    /// Maximum number of requests aligned concurrently. Further requests wait for a worker to become
+    /// available. Defaults to `4`
+    pub concurrency: usize,
+    /// Maximum size in bytes of a request body. Larger requests are rejected. Defaults to `16 MiB`
+    pub max_body: usize,
+}
+
+impl Default for Server {
+    fn default() -> Self {
+        Self {
+            config: Box::new(Config::default),
+            concurrency: 4,
+            max_body: 16 * 1024 * 1024,
+        }
+    }
+}
+
+impl Server {
+    /// Listens on `address` and serves alignment requests until the listener fails
+    pub fn serve(&self, address: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let server = tiny_http::Server::http(address)?;
+        thread::scope(|scope| {
+            for _ in 0..self.concurrency.max(1) {
+                scope.spawn(|| {
+                    for request in server.incoming_requests() {
+                        self.handle(request);
+                    }
+                });
+            }
+        });
+
+        Ok(())
+    }
+
+    fn handle(&self, mut request: Request) {
+        let (status, body) = if request.url() != "/align" {
+            (404, json!({ "error": "not found" }))
+        } else if *request.method() != Method::Post {
+            (405, json!({ "error": "method not allowed" }))
+        } else if request.body_length().unwrap_or(0) > self.max_body {
+            (413, json!({ "error": "request body too large" }))
+        } else {
+            let mut text = String::new();
+            match request
+                .as_reader()
+                .take(self.max_body as u64 + 1)
+                .read_to_string(&mut text)
+            {
+                Ok(n) if n > self.max_body => (413, json!({ "error": "request body too large" })),
+                // A request which panics the alignment fails on its own, keeping the worker serving
+                Ok(_) => match panic::catch_unwind(AssertUnwindSafe(|| self.align(&text))) {
+                    Ok(Ok(body)) => (200, body),
+                    Ok(Err(error)) => (400, json!({ "error": error })),
+                    Err(_) => (500, json!({ "error": "alignment failed" })),
+                },
+                Err(error) => (400, json!({ "error": error.to_string() })),
+            }
+        };
+
+        let response = Response::from_string(body.to_string())
+            .with_status_code(status)
+            .with_header(
+                Header::from_bytes("Content-Type", "application/json")
+                    .expect("static header is valid"),
+            );
+        // The client may have disconnected, in which case there is nobody left to notify
+        let _ = request.respond(response);
+    }
+
+    fn align(&self, text: &str) -> Result<Value, String> {
+        let body = serde_json::from_str::<Value>(text).map_err(|error| error.to_string())?;
+        let a = sentences(&body, "a")?;
+        let b = sentences(&body, "b")?;
+
+        let output = (self.config)().align(&a, &b);
+        Ok(json!({
+            "pairs": output.pairs().map(|pair| [pair.a, pair.b]).collect::<Vec<_>>(),
+            "coverage": output.coverage().last().copied().unwrap_or(0.0),
+        }))
+    }
+}
+
+struct Words(Vec<String>);
+
+impl Sentence<String> for Words {
+    fn words(&self) -> &[String] {
+        &self.0
+    }
+}
+
+fn sentences(body: &Value, key: &str) -> Result<Vec<Words>, String> {
+    let sentences = body
+        .get(key)
+        .and_then(Value::as_array)
+        .ok_or_else(|| format!("expected an array of sentences at `{key}`"))?;
+    if sentences.is_empty() {
+        return Err(format!("expected at least one sentence at `{key}`"));
+    }
+
+    sentences
+        .iter()
+        .map(|sentence| match sentence {
+            Value::String(text) => Ok(Words(text.split_whitespace().map(String::from).collect())),
+            Value::Array(words) => words
+                .iter()
+                .map(|word| word.as_str().map(String::from))
+                .collect::<Option<_>>()
+                .map(Words)
+                .ok_or_else(|| format!("expected the words of `{key}` to be strings")),
+            _ => Err(format!(
+                "expected the sentences of `{key}` to be strings or arrays of words"
+            )),
+        })
+        .collect()
+}
@@ -0,0 +1,68 @@
+use std::hash::Hash;
+use std::ops::Deref;
+use std::sync::Arc;
+
+use super::{Config, Output, Sentence};
+
+/// Sentences of a text, either borrowed from the caller or shared with them
+pub(crate) enum Text<'a, T> {
+    Borrowed(&'a [T]),
+    Shared(Arc<[T]>),
+}
+
+impl<T> Clone for Text<'_, T> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Borrowed(text) => Self::Borrowed(text),
+            Self::Shared(text) => Self::Shared(text.clone()),
+        }
+    }
+}
+
+impl<T> Deref for Text<'_, T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        match self {
+            Self::Borrowed(text) => text,
+            Self::Shared(text) => text,
+        }
+    }
+}
+
+impl<Word> Config<Word> {
+    /// Aligns `a` and `b` as `Config::align`, returning an output which holds the texts rather than
+    /// borrowing them, so that it may be kept alive independently of the caller, e.g. across the await
+    /// points of an async service
+    pub fn align_shared<T, U>(self, a: Arc<[T]>, b: Arc<[U]>) -> Output<'static, T, U>
+    where
+        Word: Eq + Hash,
+        T: Sentence<Word>,
+        U: Sentence<Word>,
+    {
+        self.align(&a, &b)
+            .with_texts(Text::Shared(a.clone()), Text::Shared(b.clone()))
+    }
+}
+
+impl<T, U> Output<'_, T, U> {
+    /// Returns the output for the same sentences held in `a` and `b`
+    pub(crate) fn with_texts<'b>(self, a: Text<'b, T>, b: Text<'b, U>) -> Output<'b, T, U> {
+        Output {
+            a,
+            b,
+            a_alignments: self.a_alignments,
+            b_alignments: self.b_alignments,
+            coverage: self.coverage,
+            sat: self.sat,
+            abridgement: self.abridgement,
+            outliers: self.outliers,
+            snapshots: self.snapshots,
+            provenance: self.provenance,
+            moves: self.moves,
+            quality: self.quality,
+            translations: self.translations,
+            boilerplate: self.boilerplate,
+        }
+    }
+}
@@ -0,0 +1,46 @@
+/// Derivation of each cycle's word similarity threshold from the distribution of the similarities of the
+/// associations eligible in that cycle, rather than a fixed value and taper. The threshold is set so that
+/// a share of the eligible associations, most similar first, enters the WAT, which self-calibrates
+/// across corpora whose similarities differ widely, e.g. loose paraphrases and literal translations
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SimilarityQuantiles {
+    /// Share of the eligible associations, most similar first, entering the WAT in the first cycle.
+    /// Defaults to `0.05`
+    pub initial_share: f32,
+    /// Share of the eligible associations entering the WAT once the share has been widened.
+    /// Defaults to `0.5`
+    pub final_share: f32,
+    /// Number of cycles over which the share is widened from the `initial_share` to the `final_share`.
+    /// Defaults to `5`
+    pub cycles: usize,
+}
+
+impl Default for SimilarityQuantiles {
+    fn default() -> Self {
+        Self {
+            initial_share: 0.05,
+            final_share: 0.5,
+            cycles: 5,
+        }
+    }
+}
+
+impl SimilarityQuantiles {
+    /// Returns the similarity threshold of the cycle `cycle` for the similarities of the eligible
+    /// associations, or `None` where there are none
+    pub(crate) fn threshold(
+        &self,
+        cycle: usize,
+        similarities: impl IntoIterator<Item = f32>,
+    ) -> Option<f32> {
+        let mut similarities = similarities.into_iter().collect::<Vec<_>>();
+        similarities.sort_unstable_by(|a, b| b.total_cmp(a));
+
+        let cycles = self.cycles.max(1);
+        let progress = cycle.min(cycles) as f32 / cycles as f32;
+        let share = self.initial_share + (self.final_share - self.initial_share) * progress;
+        let rank = (share.clamp(0.0, 1.0) * similarities.len() as f32).ceil() as usize;
+
+        similarities.get(rank.saturating_sub(1)).copied()
+    }
+}
@@ -0,0 +1,62 @@
+use super::{AlignedPair, Coordinates, Output, SentenceAlignmentTable, X, Y};
+
+/// The SAT as it stood at the end of a cycle. Snapshots share the unchanged rows of the table with one
+/// another, so taking one each cycle is cheap
+#[derive(Debug, Clone)]
+pub struct Snapshot {
+    cycle: usize,
+    sat: SentenceAlignmentTable,
+}
+
+impl Snapshot {
+    pub(crate) fn new(cycle: usize, sat: &SentenceAlignmentTable) -> Self {
+        Self {
+            cycle,
+            sat: sat.clone(),
+        }
+    }
+
+    pub(crate) fn sat(&self) -> &SentenceAlignmentTable {
+        &self.sat
+    }
+
+    /// Returns the cycle at the end of which the snapshot was taken
+    pub fn cycle(&self) -> usize {
+        self.cycle
+    }
+
+    /// Returns the score of the pair of sentence indices `(a, b)`
+    pub fn score(&self, a: usize, b: usize) -> usize {
+        self.sat.score(Coordinates(X(b), Y(a))).0
+    }
+
+    /// Returns an iterator of the pairs with a non-zero score
+    pub fn cells(&self) -> impl Iterator<Item = AlignedPair> + '_ {
+        self.sat
+            .cells()
+            .map(|(coordinates, score)| AlignedPair::new(coordinates, score))
+    }
+
+    /// Returns an iterator of the pairs which were anchors
+    pub fn anchors(&self) -> impl Iterator<Item = AlignedPair> + '_ {
+        self.sat
+            .anchors()
+            .map(|coordinates| AlignedPair::new(coordinates, self.sat.score(coordinates)))
+    }
+}
+
+impl<'a, T, U> Output<'a, T, U> {
+    /// Returns the snapshots of the SAT taken at the end of each cycle. Always empty unless the texts were
+    /// aligned with `Config::snapshots` enabled
+    pub fn snapshots(&self) -> &[Snapshot] {
+        &self.snapshots
+    }
+
+    /// Returns an iterator of the pairs with a non-zero score in the final SAT, e.g. to resume from with
+    /// `Config::align_from`
+    pub fn cells(&self) -> impl Iterator<Item = AlignedPair> + '_ {
+        self.sat
+            .cells()
+            .map(|(coordinates, score)| AlignedPair::new(coordinates, score))
+    }
+}
@@ -0,0 +1,102 @@
+use std::io::{self, Write};
+use std::ops::Range;
+
+use super::{AlignedPair, Output, Sentence};
+
+/// A sentence with the range of bytes it occupies in its original document, for sentences which do not
+/// track their own `Sentence::span`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Spanned<S> {
+    pub sentence: S,
+    pub span: Range<usize>,
+}
+
+impl<S> Spanned<S> {
+    pub fn new(sentence: S, span: Range<usize>) -> Self {
+        Self { sentence, span }
+    }
+}
+
+impl<Word: PartialEq, S: Sentence<Word>> Sentence<Word> for Spanned<S> {
+    fn words(&self) -> &[Word] {
+        self.sentence.words()
+    }
+
+    fn span(&self) -> Option<Range<usize>> {
+        Some(self.span.clone())
+    }
+}
+
+/// An aligned pair with the ranges of bytes of its sentences in their original documents, where known
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SpannedPair {
+    pub pair: AlignedPair,
+    /// Range of bytes of the sentence of text `a` in its document
+    pub a: Option<Range<usize>>,
+    /// Range of bytes of the sentence of text `b` in its document
+    pub b: Option<Range<usize>>,
+}
+
+impl<'a, T, U> Output<'a, T, U> {
+    /// Returns the range of bytes in its original document of the sentence of text `a` at index `a`
+    pub fn a_span<Word>(&self, a: usize) -> Option<Range<usize>>
+    where
+        Word: PartialEq,
+        T: Sentence<Word>,
+    {
+        self.a.get(a).and_then(Sentence::span)
+    }
+
+    /// Returns the range of bytes in its original document of the sentence of text `b` at index `b`
+    pub fn b_span<Word>(&self, b: usize) -> Option<Range<usize>>
+    where
+        Word: PartialEq,
+        U: Sentence<Word>,
+    {
+        self.b.get(b).and_then(Sentence::span)
+    }
+
+    /// Returns the aligned pairs, ordered as `pairs`, with the spans of their sentences, e.g. to highlight
+    /// the corresponding passages of the original documents
+    pub fn spanned_pairs<Word>(&self) -> impl Iterator<Item = SpannedPair> + '_
+    where
+        Word: PartialEq,
+        T: Sentence<Word>,
+        U: Sentence<Word>,
+    {
+        self.pairs().map(|pair| SpannedPair {
+            pair,
+            a: self.a_span(pair.a),
+            b: self.b_span(pair.b),
+        })
+    }
+
+    /// Writes the `spanned_pairs` to `writer` with one pair per line as tab separated `a`, `b`, `score`
+    /// and the start and end of the span of each sentence, empty where unknown
+    pub fn export_spans<Word>(&self, mut writer: impl Write) -> io::Result<()>
+    where
+        Word: PartialEq,
+        T: Sentence<Word>,
+        U: Sentence<Word>,
+    {
+        let bounds = |span: Option<Range<usize>>| {
+            span.map_or_else(
+                || "\t".to_owned(),
+                |span| format!("{}\t{}", span.start, span.end),
+            )
+        };
+        for spanned in self.spanned_pairs() {
+            writeln!(
+                writer,
+                "{}\t{}\t{}\t{}\t{}",
+                spanned.pair.a,
+                spanned.pair.b,
+                spanned.pair.score,
+                bounds(spanned.a),
+                bounds(spanned.b)
+            )?;
+        }
+
+        Ok(())
+    }
+}
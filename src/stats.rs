@@ -0,0 +1,85 @@
+use std::collections::HashSet;
+
+use super::{Coordinates, Output};
+
+/// Summary statistics of an alignment, see `Output::stats`
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Stats {
+    /// Number of blocks aligning one sentence of text `a` to one sentence of text `b`
+    pub one_to_one: usize,
+    /// Number of blocks aligning one sentence of text `a` to several sentences of text `b`
+    pub one_to_many: usize,
+    /// Number of blocks aligning several sentences of text `a` to one sentence of text `b`
+    pub many_to_one: usize,
+    /// Number of blocks aligning several sentences of both texts
+    pub many_to_many: usize,
+    /// Number of sentences of text `a` without any alignment
+    pub a_unaligned: usize,
+    /// Number of sentences of text `b` without any alignment
+    pub b_unaligned: usize,
+    /// Fraction of the sentences of text `a` with an alignment
+    pub a_coverage: f32,
+    /// Fraction of the sentences of text `b` with an alignment
+    pub b_coverage: f32,
+    /// Mean score of the aligned pairs
+    pub mean_anchor_score: f32,
+    /// Number of cycles performed
+    pub cycles: usize,
+}
+
+impl<'a, T, U> Output<'a, T, U> {
+    /// Returns summary statistics of the alignment, counting the blocks of sentences connected to one
+    /// another by alignments
+    pub fn stats(&self) -> Stats {
+        let mut stats = Stats {
+            a_unaligned: self.a.len() - self.a_alignments.len(),
+            b_unaligned: self.b.len() - self.b_alignment_map().len(),
+            a_coverage: self.a_alignments.len() as f32 / self.a.len().max(1) as f32,
+            b_coverage: self.b_alignment_map().len() as f32 / self.b.len().max(1) as f32,
+            cycles: self.coverage.len(),
+            ..Default::default()
+        };
+
+        let mut a_visited = HashSet::new();
+        let mut b_visited = HashSet::new();
+        for &start in self.a_alignments.keys() {
+            if !a_visited.insert(start) {
+                continue;
+            }
+
+            let (mut a_size, mut b_size) = (1, 0);
+            let mut stack = vec![start];
+            while let Some(y) = stack.pop() {
+                for &x in &self.a_alignments[&y] {
+                    if b_visited.insert(x) {
+                        b_size += 1;
+                        for &y in &self.b_alignment_map()[&x] {
+                            if a_visited.insert(y) {
+                                a_size += 1;
+                                stack.push(y);
+                            }
+                        }
+                    }
+                }
+            }
+
+            match (a_size, b_size) {
+                (1, 1) => stats.one_to_one += 1,
+                (1, _) => stats.one_to_many += 1,
+                (_, 1) => stats.many_to_one += 1,
+                _ => stats.many_to_many += 1,
+            }
+        }
+
+        let scores = self
+            .a_alignments
+            .iter()
+            .flat_map(|(&y, xs)| xs.iter().map(move |&x| self.sat.score(Coordinates(x, y)).0))
+            .collect::<Vec<_>>();
+        if !scores.is_empty() {
+            stats.mean_anchor_score = scores.iter().sum::<usize>() as f32 / scores.len() as f32;
+        }
+
+        stats
+    }
+}
@@ -0,0 +1,78 @@
+use super::{Coordinates, Gap, Output, Side, X, Y};
+
+/// A sentence of the other text aligned to a sentence
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Partner {
+    /// Index of the sentence in the other text
+    pub index: usize,
+    /// Score of the alignment in the SAT
+    pub score: usize,
+}
+
+/// Classification of a sentence by the outcome of the alignment
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    /// The sentence is aligned to the given partners, ordered by index
+    Aligned(Vec<Partner>),
+    /// The sentence lies inside a gap whose bounding anchors leave no sentences of the other text, so it
+    /// has no counterpart
+    Gap,
+    /// The sentence is not aligned, but sentences of the other text remain between its bounding anchors
+    Undecided,
+}
+
+impl<'a, T, U> Output<'a, T, U> {
+    /// Returns the status of the sentence at index `i` of text `a`
+    pub fn a_status(&self, i: usize) -> Status {
+        self.status(Side::A, i, &self.gaps())
+    }
+
+    /// Returns the status of the sentence at index `i` of text `b`
+    pub fn b_status(&self, i: usize) -> Status {
+        self.status(Side::B, i, &self.gaps())
+    }
+
+    /// Returns an iterator of the status of every sentence of text `a`, in text order
+    pub fn a_statuses(&self) -> impl Iterator<Item = Status> + '_ {
+        let gaps = self.gaps();
+        (0..self.a.len()).map(move |i| self.status(Side::A, i, &gaps))
+    }
+
+    /// Returns an iterator of the status of every sentence of text `b`, in text order
+    pub fn b_statuses(&self) -> impl Iterator<Item = Status> + '_ {
+        let gaps = self.gaps();
+        (0..self.b.len()).map(move |i| self.status(Side::B, i, &gaps))
+    }
+
+    fn status(&self, side: Side, i: usize, gaps: &[Gap]) -> Status {
+        let partners = match side {
+            Side::A => self.a_alignments.get(&Y(i)).map(|xs| {
+                xs.iter()
+                    .map(|&x| Partner {
+                        index: x.0,
+                        score: self.sat.score(Coordinates(x, Y(i))).0,
+                    })
+                    .collect::<Vec<_>>()
+            }),
+            Side::B => self.b_alignment_map().get(&X(i)).map(|ys| {
+                ys.iter()
+                    .map(|&y| Partner {
+                        index: y.0,
+                        score: self.sat.score(Coordinates(X(i), y)).0,
+                    })
+                    .collect()
+            }),
+        };
+
+        match partners {
+            Some(partners) => Status::Aligned(partners),
+            None if gaps.iter().any(|gap| {
+                gap.side == side && gap.range.contains(&i) && gap.opposite.is_empty()
+            }) =>
+            {
+                Status::Gap
+            }
+            None => Status::Undecided,
+        }
+    }
+}
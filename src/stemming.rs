@@ -0,0 +1,35 @@
+use super::Normalizer;
+
+/// Alignment in two passes, the first cycles associating the stems of the words to bootstrap anchors in
+/// morphologically rich languages, where the inflections of a word are each too rare to enter the WAT, and
+/// the later cycles associating their surface forms for precision
+pub struct Stemming<Word> {
+    /// Maps the words of text `a` to their stems, e.g. a Snowball stemmer for the language of the text
+    pub a_stemmer: Normalizer<Word>,
+    /// Maps the words of text `b` to their stems
+    pub b_stemmer: Normalizer<Word>,
+    /// Number of cycles performed on the stems before switching to the surface forms. Defaults to `2`
+    pub cycles: usize,
+}
+
+impl<Word: AsRef<str>> Default for Stemming<Word> {
+    fn default() -> Self {
+        Self::prefixes(4)
+    }
+}
+
+impl<Word: AsRef<str>> Stemming<Word> {
+    /// Stems words by lowercasing them and truncating them to their first `len` characters, a crude but
+    /// language independent stemmer
+    pub fn prefixes(len: usize) -> Self {
+        let stemmer = move || -> Normalizer<Word> {
+            Box::new(move |word: &Word| word.as_ref().to_lowercase().chars().take(len).collect())
+        };
+
+        Self {
+            a_stemmer: stemmer(),
+            b_stemmer: stemmer(),
+            cycles: 2,
+        }
+    }
+}
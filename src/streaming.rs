@@ -0,0 +1,82 @@
+use std::hash::Hash;
+
+use super::sentence_alignment_table::{Score, SentenceAlignmentTable};
+use super::{Config, Output, Sentence};
+
+/// Alignment of texts to which sentences are appended over time, such as a live transcript aligned
+/// against a prepared translation. Each alignment resumes from the anchors found by the previous one
+/// rather than restarting, so that only the evidence involving the new sentences needs to be gathered.
+/// As with `Config::align`, the texts received so far are assumed to correspond to each other, so both
+/// should grow in step
+pub struct StreamingAlignment<Word, T, U> {
+    config: Box<dyn Fn() -> Config<Word>>,
+    a: Vec<T>,
+    b: Vec<U>,
+    sat: Option<SentenceAlignmentTable>,
+}
+
+impl<Word, T, U> StreamingAlignment<Word, T, U> {
+    /// Creates an empty alignment, performed with the configuration provided by `config` each time
+    pub fn new(config: impl Fn() -> Config<Word> + 'static) -> Self {
+        Self {
+            config: Box::new(config),
+            a: vec![],
+            b: vec![],
+            sat: None,
+        }
+    }
+
+    /// Appends a sentence to text `a`
+    pub fn push_a(&mut self, sentence: T) {
+        self.a.push(sentence);
+    }
+
+    /// Appends a sentence to text `b`
+    pub fn push_b(&mut self, sentence: U) {
+        self.b.push(sentence);
+    }
+
+    /// Appends sentences to text `a`
+    pub fn extend_a(&mut self, sentences: impl IntoIterator<Item = T>) {
+        self.a.extend(sentences);
+    }
+
+    /// Appends sentences to text `b`
+    pub fn extend_b(&mut self, sentences: impl IntoIterator<Item = U>) {
+        self.b.extend(sentences);
+    }
+
+    /// Returns the sentences of text `a` received so far
+    pub fn a(&self) -> &[T] {
+        &self.a
+    }
+
+    /// Returns the sentences of text `b` received so far
+    pub fn b(&self) -> &[U] {
+        &self.b
+    }
+
+    /// Aligns the sentences received so far, keeping the anchors of the previous alignment. Evidence
+    /// below the anchor threshold is gathered afresh, so that repeated alignments do not accumulate it.
+    /// Both texts must contain at least one sentence
+    pub fn align(&mut self) -> Output<'_, T, U>
+    where
+        Word: Eq + Hash,
+        T: Sentence<Word>,
+        U: Sentence<Word>,
+    {
+        let config = (self.config)();
+        let initial = self.sat.take().map(|mut sat| {
+            sat.set_anchor_threshold(Score::from(config.anchor_threshold));
+            sat.raise_locked();
+            sat.retain_anchors();
+            sat.resize(self.a.len(), self.b.len());
+            sat
+        });
+
+        let output = config.run(&self.a, &self.b, initial);
+        self.sat = Some(output.sat.clone());
+
+        output
+    }
+}
@@ -0,0 +1,90 @@
+use std::hash::Hash;
+
+use super::hash::HashSet;
+use super::lexicon::{support, Lexicon};
+use super::{Coordinates, Sentence, SentenceAlignmentTable, WordSentenceIndex, X, Y};
+
+/// Recovery of adjacent sentences translated in swapped order, performed once the cycles have finished.
+/// When an anchor pairs `a[i]` with `b[j + 1]` while `a[i + 1]` and `b[j]` are left unaligned, the pair
+/// `(a[i + 1], b[j])` crosses the anchor and so could never be captured by the cycles
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SwapRecovery {
+    /// Fraction of the words of the shorter sentence of a swapped pair which must have a counterpart in the
+    /// other sentence, among the word associations accepted while aligning, for the pair to be aligned.
+    /// Defaults to `0.3`
+    pub min_support: f32,
+}
+
+impl Default for SwapRecovery {
+    fn default() -> Self {
+        Self { min_support: 0.3 }
+    }
+}
+
+impl SwapRecovery {
+    /// Anchors the pairs swapped with an anchor which are sufficiently supported by the `lexicon`,
+    /// returning them
+    pub(crate) fn recover<'a, Word, T, U>(
+        &self,
+        sat: &mut SentenceAlignmentTable,
+        a: &'a [T],
+        b: &'a [U],
+        a_word_sentence_index: &WordSentenceIndex<'a, Word, Y>,
+        b_word_sentence_index: &WordSentenceIndex<'a, Word, X>,
+        lexicon: &Lexicon<'a, Word>,
+    ) -> Vec<Coordinates>
+    where
+        Word: Eq + Hash,
+        T: Sentence<Word>,
+        U: Sentence<Word>,
+    {
+        let pair_support = |Coordinates(X(x), Y(y)): Coordinates| {
+            let a_words = a[y]
+                .words()
+                .iter()
+                .filter_map(|word| a_word_sentence_index.indexed(word))
+                .collect::<HashSet<_>>();
+            let b_words = b[x]
+                .words()
+                .iter()
+                .filter_map(|word| b_word_sentence_index.indexed(word))
+                .collect::<HashSet<_>>();
+            support(lexicon, &a_words, &b_words)
+        };
+
+        let mut anchors = sat.anchors().collect::<Vec<_>>();
+        anchors.sort_unstable_by_key(|&Coordinates(x, y)| (y, x));
+        let mut a_aligned = anchors.iter().map(|c| c.y()).collect::<HashSet<_>>();
+        let mut b_aligned = anchors.iter().map(|c| c.x()).collect::<HashSet<_>>();
+
+        let mut recovered = vec![];
+        for &Coordinates(X(x), Y(y)) in &anchors {
+            // The pair below and to the left of the anchor, then the pair above and to the right
+            let swapped = [
+                (x.checked_sub(1), Some(y + 1).filter(|&y| y < a.len())),
+                (Some(x + 1).filter(|&x| x < b.len()), y.checked_sub(1)),
+            ];
+            for (x, y) in swapped {
+                let (Some(x), Some(y)) = (x, y) else {
+                    continue;
+                };
+                let coordinates = Coordinates(X(x), Y(y));
+                if a_aligned.contains(&Y(y))
+                    || b_aligned.contains(&X(x))
+                    || sat.is_forbidden(coordinates)
+                    || sat.crosses_locked(coordinates)
+                    || pair_support(coordinates) < self.min_support
+                {
+                    continue;
+                }
+
+                sat.anchor(coordinates);
+                a_aligned.insert(Y(y));
+                b_aligned.insert(X(x));
+                recovered.push(coordinates);
+            }
+        }
+
+        recovered
+    }
+}
@@ -0,0 +1,192 @@
+//! Generation of pseudo-parallel texts with a known alignment, for benchmarking parameter settings
+
+use std::ops::Range;
+
+use super::Sentence;
+
+/// A generated sentence
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyntheticSentence(pub Vec<String>);
+
+impl Sentence<String> for SyntheticSentence {
+    fn words(&self) -> &[String] {
+        &self.0
+    }
+}
+
+/// A generated pair of texts with the pairs of sentence indices `(a, b)` of their true alignment
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Corpus {
+    pub a: Vec<SyntheticSentence>,
+    pub b: Vec<SyntheticSentence>,
+    /// True alignment, ordered by `a`
+    pub gold: Vec<(usize, usize)>,
+}
+
+/// Generator of pseudo-parallel texts. Text `b` is a word-for-word "translation" of text `a` through a
+/// fixed lexicon, distorted by the configured noise
+#[derive(Debug, Clone, PartialEq)]
+pub struct Generator {
+    /// Number of sentences of text `a`. Defaults to `200`
+    pub sentences: usize,
+    /// Number of distinct words of each text, drawn with a heavy-tailed distribution. Defaults to `2000`
+    pub vocabulary: usize,
+    /// Range of the number of words of each sentence of text `a`. Defaults to `5..20`
+    pub sentence_length: Range<usize>,
+    /// Probability that a word is translated by its counterpart in the lexicon rather than by a random
+    /// word. Defaults to `0.8`
+    pub overlap: f32,
+    /// Probability that a sentence of text `a` has no counterpart in text `b`. Defaults to `0.05`
+    pub deletion_rate: f32,
+    /// Probability that a sentence without counterpart is inserted in text `b` after each sentence.
+    /// Defaults to `0.02`
+    pub insertion_rate: f32,
+    /// Probability that a sentence of text `a` is merged with the next into one sentence of text `b`.
+    /// Defaults to `0.03`
+    pub merge_rate: f32,
+    /// Probability that a sentence of text `a` is split into two sentences of text `b`. Defaults to `0.03`
+    pub split_rate: f32,
+    /// Probability that a sentence of text `b` is swapped with the next. Defaults to `0.01`
+    pub reorder_rate: f32,
+    /// Seed of the pseudo-random generation. Defaults to `1`
+    pub seed: u64,
+}
+
+impl Default for Generator {
+    fn default() -> Self {
+        Self {
+            sentences: 200,
+            vocabulary: 2000,
+            sentence_length: 5..20,
+            overlap: 0.8,
+            deletion_rate: 0.05,
+            insertion_rate: 0.02,
+            merge_rate: 0.03,
+            split_rate: 0.03,
+            reorder_rate: 0.01,
+            seed: 1,
+        }
+    }
+}
+
+impl Generator {
+    /// Generates a pair of texts and their true alignment
+    pub fn generate(&self) -> Corpus {
+        let mut rng = Rng(self.seed.max(1));
+        let a = (0..self.sentences)
+            .map(|_| {
+                let length = rng.range(self.sentence_length.clone());
+                SyntheticSentence(
+                    (0..length)
+                        .map(|_| format!("a{}", self.word(&mut rng)))
+                        .collect(),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let mut b = vec![];
+        let mut gold = vec![];
+        let mut i = 0;
+        while i < a.len() {
+            if rng.chance(self.deletion_rate) {
+                i += 1;
+            } else if i + 1 < a.len() && rng.chance(self.merge_rate) {
+                let mut words = self.translate(&a[i], &mut rng);
+                words.extend(self.translate(&a[i + 1], &mut rng));
+                gold.extend([(i, b.len()), (i + 1, b.len())]);
+                b.push(SyntheticSentence(words));
+                i += 2;
+            } else if a[i].0.len() > 1 && rng.chance(self.split_rate) {
+                let mut first = self.translate(&a[i], &mut rng);
+                let second = first.split_off(first.len() / 2);
+                gold.extend([(i, b.len()), (i, b.len() + 1)]);
+                b.extend([SyntheticSentence(first), SyntheticSentence(second)]);
+                i += 1;
+            } else {
+                gold.push((i, b.len()));
+                b.push(SyntheticSentence(self.translate(&a[i], &mut rng)));
+                i += 1;
+            }
+
+            if rng.chance(self.insertion_rate) {
+                let length = rng.range(self.sentence_length.clone());
+                b.push(SyntheticSentence(
+                    (0..length)
+                        .map(|_| format!("b{}", self.word(&mut rng)))
+                        .collect(),
+                ));
+            }
+        }
+
+        let mut positions = (0..b.len()).collect::<Vec<_>>();
+        let mut j = 0;
+        while j + 1 < b.len() {
+            if rng.chance(self.reorder_rate) {
+                b.swap(j, j + 1);
+                positions.swap(j, j + 1);
+                j += 2;
+            } else {
+                j += 1;
+            }
+        }
+        let mut moved = vec![0; b.len()];
+        for (position, &original) in positions.iter().enumerate() {
+            moved[original] = position;
+        }
+        for (_, j) in &mut gold {
+            *j = moved[*j];
+        }
+        gold.sort_unstable();
+
+        Corpus { a, b, gold }
+    }
+
+    /// Returns the index of a word, drawn log-uniformly so that few words are frequent and most are rare
+    fn word(&self, rng: &mut Rng) -> usize {
+        let vocabulary = self.vocabulary.max(1) as f64;
+        (vocabulary.powf(rng.unit()) as usize).saturating_sub(1)
+    }
+
+    fn translate(&self, sentence: &SyntheticSentence, rng: &mut Rng) -> Vec<String> {
+        sentence
+            .0
+            .iter()
+            .map(|word| {
+                if rng.chance(self.overlap) {
+                    format!("b{}", &word[1..])
+                } else {
+                    format!("b{}", self.word(rng))
+                }
+            })
+            .collect()
+    }
+}
+
+/// xorshift64* generator
+pub(crate) struct Rng(pub(crate) u64);
+
+impl Rng {
+    pub(crate) fn next(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    /// Returns a number uniformly distributed in `[0, 1)`
+    pub(crate) fn unit(&mut self) -> f64 {
+        (self.next() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    pub(crate) fn chance(&mut self, probability: f32) -> bool {
+        self.unit() < f64::from(probability)
+    }
+
+    pub(crate) fn range(&mut self, range: Range<usize>) -> usize {
+        if range.is_empty() {
+            range.start
+        } else {
+            range.start + (self.next() % range.len() as u64) as usize
+        }
+    }
+}
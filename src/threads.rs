@@ -0,0 +1,112 @@
+use std::hash::Hash;
+use std::num::NonZeroUsize;
+use std::panic;
+use std::thread::{self, ScopedJoinHandle};
+
+use super::word_association::{Similarity, WordAssociation};
+use super::{AlignableSentenceTable, Config, Indices, Output, Sentence, WordSentenceIndex, X, Y};
+
+/// Parallelism on plain scoped `std::thread`s, for consumers who cannot depend on rayon. The indices of
+/// the texts are built on their own threads, and the similarities of the word pairs of each cycle are
+/// computed on up to `count` threads. The feature requires the `Normalizer`s and `WordFilter`s of the
+/// configuration to be `Send + Sync`, as they are shared by the threads building the indices
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Threads {
+    /// Number of threads. Defaults to the available parallelism
+    pub count: NonZeroUsize,
+}
+
+impl Default for Threads {
+    fn default() -> Self {
+        Self {
+            count: thread::available_parallelism().unwrap_or(NonZeroUsize::MIN),
+        }
+    }
+}
+
+impl Threads {
+    /// Aligns the texts `a` and `b` as `config.align` would, building the index of each text (and of its
+    /// stems) on its own thread. The similarities are computed on the calling thread when the
+    /// configuration has an `ensemble`
+    pub fn align<'a, Word, T, U>(
+        &self,
+        a: &'a [T],
+        b: &'a [U],
+        config: Config<Word>,
+    ) -> Output<'a, T, U>
+    where
+        Word: Eq + Hash + Sync + 'a,
+        T: Sentence<Word> + Sync,
+        U: Sentence<Word> + Sync,
+    {
+        let boilerplate = config.boilerplate(a, b);
+        let (a_excluded, b_excluded) = (&boilerplate.0 .1, &boilerplate.1 .1);
+        let stemmed = config.stemming.is_some();
+        let (a_indexing, b_indexing) = (config.a_indexing(false), config.b_indexing(false));
+        let (a_stemming, b_stemming) = (config.a_indexing(true), config.b_indexing(true));
+
+        let indices = if self.count.get() == 1 {
+            Indices {
+                a: a_indexing.index(a, a_excluded),
+                b: b_indexing.index(b, b_excluded),
+                a_stemmed: stemmed.then(|| a_stemming.index(a, a_excluded)),
+                b_stemmed: stemmed.then(|| b_stemming.index(b, b_excluded)),
+                scorer: None,
+            }
+        } else {
+            thread::scope(|scope| {
+                let a_index = scope.spawn(|| a_indexing.index(a, a_excluded));
+                let b_index = scope.spawn(|| b_indexing.index(b, b_excluded));
+                let a_stemmed = stemmed.then(|| scope.spawn(|| a_stemming.index(a, a_excluded)));
+                let b_stemmed = stemmed.then(|| scope.spawn(|| b_stemming.index(b, b_excluded)));
+
+                Indices {
+                    a: join(a_index),
+                    b: join(b_index),
+                    a_stemmed: a_stemmed.map(join),
+                    b_stemmed: b_stemmed.map(join),
+                    scorer: Some((similarities::<Word>, self.count)),
+                }
+            })
+        };
+
+        config.run_indexed(a, b, None, boilerplate, indices)
+    }
+}
+
+/// Returns the co-occurrence similarities of the `pairs`, computed on `threads` threads each scoring a
+/// contiguous share of them
+fn similarities<'w, Word>(
+    ast: &AlignableSentenceTable,
+    a_index: &WordSentenceIndex<'w, Word, Y>,
+    b_index: &WordSentenceIndex<'w, Word, X>,
+    pairs: &[(&'w Word, &'w Word)],
+    threads: NonZeroUsize,
+) -> Vec<Similarity>
+where
+    Word: Eq + Hash + Sync,
+{
+    let share = pairs.len().div_ceil(threads.get()).max(1);
+    thread::scope(|scope| {
+        let handles = pairs
+            .chunks(share)
+            .map(|pairs| {
+                scope.spawn(move || {
+                    pairs
+                        .iter()
+                        .map(|&(a, b)| WordAssociation::similarity(ast, a_index, b_index, a, b))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect::<Vec<_>>();
+
+        handles.into_iter().flat_map(join).collect()
+    })
+}
+
+/// Returns the result of a thread, resuming its panic if it panicked
+fn join<T>(handle: ScopedJoinHandle<'_, T>) -> T {
+    handle
+        .join()
+        .unwrap_or_else(|payload| panic::resume_unwind(payload))
+}
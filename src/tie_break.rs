@@ -0,0 +1,54 @@
+use std::cmp::{Ordering, Reverse};
+
+use super::WordAssociation;
+
+/// Comparison of the pairs of words `(a, b)` of two associations
+pub type PairOrdering<Word> =
+    Box<dyn for<'a> Fn((&'a Word, &'a Word), (&'a Word, &'a Word)) -> Ordering>;
+
+/// Order in which associations of equal similarity are processed. Ties remaining after the chosen order
+/// are broken by the order in which the associations were discovered
+#[derive(Default)]
+pub enum TieBreak<Word> {
+    /// Associations of more frequent words first
+    #[default]
+    Frequency,
+    /// Associations of rarer words first
+    Rarity,
+    /// Associations in the order in which they were discovered in the corridor
+    Discovery,
+    /// Associations ordered by a comparison of their pairs of words, earlier pairs first
+    Custom(PairOrdering<Word>),
+}
+
+impl<Word: Ord> TieBreak<Word> {
+    /// Associations ordered lexicographically by their pairs of words
+    pub fn lexicographic() -> Self {
+        Self::Custom(Box::new(|a, b| a.cmp(&b)))
+    }
+}
+
+impl<Word> TieBreak<Word> {
+    /// Ranks `associations`, given in the order of their discovery, by assigning each its position in
+    /// the tie-breaking order
+    pub(crate) fn rank(&self, associations: &mut [WordAssociation<'_, Word>]) {
+        let occurrences = |association: &WordAssociation<'_, Word>| {
+            association
+                .a_occurrences
+                .saturating_add(association.b_occurrences)
+        };
+
+        match self {
+            Self::Frequency => {
+                associations.sort_by_key(|association| Reverse(occurrences(association)))
+            }
+            Self::Rarity => associations.sort_by_key(occurrences),
+            Self::Discovery => {}
+            Self::Custom(ordering) => associations.sort_by(|a, b| ordering((a.a, a.b), (b.a, b.b))),
+        }
+
+        for (rank, association) in associations.iter_mut().enumerate() {
+            association.order = rank;
+        }
+    }
+}
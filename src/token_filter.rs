@@ -0,0 +1,41 @@
+use super::WordFilter;
+
+/// Classes of tokens excluded from the index, and so from the word frequencies, associations and word
+/// counts. The punctuation and numbers of tokenized corpora otherwise dominate the frequency table without
+/// indicating which sentences correspond
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct TokenFilter {
+    /// Drop the tokens made only of punctuation, symbols and whitespace. Defaults to `false`
+    pub punctuation: bool,
+    /// Drop the numbers, i.e. the tokens made only of digits and the separators within numbers, such as
+    /// "1,000.5" or "12:30". Defaults to `false`
+    pub digits: bool,
+}
+
+impl TokenFilter {
+    /// Returns true if `token` is kept by these options
+    pub fn keeps(&self, token: &str) -> bool {
+        let punctuation = !token.chars().any(char::is_alphanumeric);
+        let digits = token.chars().any(char::is_numeric)
+            && token
+                .chars()
+                .all(|c| c.is_numeric() || matches!(c, '.' | ',' | ':' | '/' | '-' | '+' | '%'));
+
+        !(self.punctuation && punctuation || self.digits && digits)
+    }
+
+    /// Returns a filter applying these options, for use as the `Config::a_word_filter` or
+    /// `Config::b_word_filter`
+    pub fn filter<Word: AsRef<str>>(self) -> WordFilter<Word> {
+        Box::new(move |word| self.keeps(word.as_ref()))
+    }
+
+    /// Returns a filter applying these options and then `other`, e.g. a stop word filter or a predicate
+    /// rejecting the tokens matching a regex
+    pub fn filter_with<Word: AsRef<str> + 'static>(
+        self,
+        other: WordFilter<Word>,
+    ) -> WordFilter<Word> {
+        Box::new(move |word| self.keeps(word.as_ref()) && other(word))
+    }
+}
@@ -0,0 +1,122 @@
+use unicode_segmentation::UnicodeSegmentation;
+
+use super::Sentence;
+
+/// A sentence of raw text, segmented into words at the Unicode word boundaries once when created, so that
+/// untokenized sentences may be aligned directly. Punctuation and whitespace are not words
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Tokenized {
+    text: String,
+    words: Vec<String>,
+}
+
+impl Tokenized {
+    /// Segments `text` into words
+    pub fn new(text: impl Into<String>) -> Self {
+        Self::segmented(text, &Segmenter::Unicode)
+    }
+
+    /// Segments `text` into words with the `segmenter`
+    pub fn segmented(text: impl Into<String>, segmenter: &Segmenter) -> Self {
+        let text = text.into();
+        let words = segmenter.segment(&text);
+
+        Self { text, words }
+    }
+
+    /// Returns the raw text of the sentence
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Segments each of the `sentences`, e.g. `Tokenized::all(lines)` for a `Vec<String>`
+    pub fn all<S: Into<String>>(sentences: impl IntoIterator<Item = S>) -> Vec<Self> {
+        sentences.into_iter().map(Self::new).collect()
+    }
+}
+
+/// The segmentation of raw text into words
+pub enum Segmenter {
+    /// Segmentation at the Unicode word boundaries, suited to the scripts which separate words with spaces
+    Unicode,
+    /// Dictionary-based segmentation of Chinese with jieba
+    #[cfg(feature = "jieba")]
+    Chinese(Box<jieba_rs::Jieba>),
+    /// Dictionary-based segmentation of Japanese with lindera
+    #[cfg(feature = "lindera")]
+    Japanese(Box<lindera::segmenter::Segmenter>),
+}
+
+impl Segmenter {
+    /// Returns the segmenter of Chinese with jieba's default dictionary
+    #[cfg(feature = "jieba")]
+    pub fn chinese() -> Self {
+        Self::Chinese(Box::new(jieba_rs::Jieba::new()))
+    }
+
+    /// Returns the segmenter of Japanese with the lindera dictionary at `dictionary`, a path or URI such as
+    /// `embedded://ipadic` when lindera's `embed-ipadic` feature is enabled
+    #[cfg(feature = "lindera")]
+    pub fn japanese(dictionary: &str) -> Result<Self, lindera::error::LinderaError> {
+        let dictionary = lindera::dictionary::load_dictionary(dictionary)?;
+        Ok(Self::Japanese(Box::new(
+            lindera::segmenter::Segmenter::new(lindera::mode::Mode::Normal, dictionary, None),
+        )))
+    }
+
+    /// Returns the words of `text`, without its punctuation and whitespace
+    pub fn segment(&self, text: &str) -> Vec<String> {
+        match self {
+            Self::Unicode => text.unicode_words().map(str::to_owned).collect(),
+            #[cfg(feature = "jieba")]
+            Self::Chinese(jieba) => jieba
+                .cut(text, true)
+                .into_iter()
+                .filter(|token| is_word(token))
+                .map(str::to_owned)
+                .collect(),
+            #[cfg(feature = "lindera")]
+            Self::Japanese(segmenter) => match segmenter.segment(text.into()) {
+                Ok(tokens) => tokens
+                    .into_iter()
+                    .filter(|token| is_word(&token.surface))
+                    .map(|token| token.surface.into_owned())
+                    .collect(),
+                // The dictionary could not be read, so fall back to the Unicode word boundaries
+                Err(_) => Self::Unicode.segment(text),
+            },
+        }
+    }
+
+    /// Segments each of the `sentences`
+    pub fn all<S: Into<String>>(&self, sentences: impl IntoIterator<Item = S>) -> Vec<Tokenized> {
+        sentences
+            .into_iter()
+            .map(|sentence| Tokenized::segmented(sentence, self))
+            .collect()
+    }
+}
+
+/// Dictionary segmenters also return the punctuation and whitespace between words as tokens
+#[cfg(any(feature = "jieba", feature = "lindera"))]
+fn is_word(token: &str) -> bool {
+    token.chars().any(char::is_alphanumeric)
+}
+
+impl From<String> for Tokenized {
+    fn from(text: String) -> Self {
+        Self::new(text)
+    }
+}
+
+impl From<&str> for Tokenized {
+    fn from(text: &str) -> Self {
+        Self::new(text)
+    }
+}
+
+impl Sentence<String> for Tokenized {
+    fn words(&self) -> &[String] {
+        &self.words
+    }
+}
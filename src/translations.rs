@@ -0,0 +1,200 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+use std::hash::Hash;
+use std::io::{self, Write};
+use std::ptr;
+
+use super::lexicon::Lexicon;
+use super::{Output, Sentence, SentenceAlignmentTable, WordSentenceIndex, X, Y};
+
+/// A word of text `b` translating a word of text `a`, induced from the aligned pairs
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Translation<'a, Word> {
+    pub a: &'a Word,
+    pub b: &'a Word,
+    /// Number of aligned pairs in which both words occur
+    pub count: usize,
+    /// Probability of `b` translating `a`, estimated from the counts of the translations of `a`
+    pub probability: f32,
+}
+
+/// A translation with its words located by the offset of an occurrence within a sentence
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Entry {
+    pub(crate) a: (usize, usize),
+    pub(crate) b: (usize, usize),
+    pub(crate) count: usize,
+    pub(crate) probability: f32,
+}
+
+/// Returns the translations of the words associated in the `lexicon`, counted over the anchors of the SAT,
+/// ordered by the first occurrence of the words of text `a` and then by decreasing probability
+pub(crate) fn induce<'a, Word, T, U>(
+    sat: &SentenceAlignmentTable,
+    a: &'a [T],
+    b: &'a [U],
+    a_word_sentence_index: &WordSentenceIndex<'a, Word, Y>,
+    b_word_sentence_index: &WordSentenceIndex<'a, Word, X>,
+    lexicon: &Lexicon<'a, Word>,
+) -> Vec<Entry>
+where
+    Word: Eq + Hash,
+    T: Sentence<Word>,
+    U: Sentence<Word>,
+{
+    let mut counts: HashMap<(&Word, &Word), usize> = HashMap::new();
+    for coordinates in sat.anchors() {
+        let b_words = b[coordinates.x().0]
+            .words()
+            .iter()
+            .filter_map(|word| b_word_sentence_index.indexed(word))
+            .collect::<HashSet<_>>();
+        let a_words = a[coordinates.y().0]
+            .words()
+            .iter()
+            .filter_map(|word| a_word_sentence_index.indexed(word))
+            .collect::<HashSet<_>>();
+        for a_word in a_words {
+            for &b_word in lexicon.get(a_word).into_iter().flatten() {
+                if b_words.contains(b_word) {
+                    *counts.entry((a_word, b_word)).or_default() += 1;
+                }
+            }
+        }
+    }
+
+    let mut totals: HashMap<&Word, usize> = HashMap::new();
+    for (&(a_word, _), &count) in &counts {
+        *totals.entry(a_word).or_default() += count;
+    }
+
+    let mut entries = counts
+        .into_iter()
+        .filter_map(|((a_word, b_word), count)| {
+            Some(Entry {
+                a: locate(a, a_word_sentence_index.sentences(a_word), a_word)?,
+                b: locate(b, b_word_sentence_index.sentences(b_word), b_word)?,
+                count,
+                probability: count as f32 / totals[a_word] as f32,
+            })
+        })
+        .collect::<Vec<_>>();
+    entries.sort_by(|x, y| {
+        x.a.cmp(&y.a)
+            .then(y.probability.total_cmp(&x.probability))
+            .then(x.b.cmp(&y.b))
+    });
+
+    entries
+}
+
+/// Returns the translations induced from each of the chapters of the texts, given with the indices of
+/// their first sentences, counting the translations found in several chapters together
+pub(crate) fn merge<'a, Word, T, U>(
+    a: &'a [T],
+    b: &'a [U],
+    chapters: impl IntoIterator<Item = (usize, usize, Vec<Entry>)>,
+) -> Vec<Entry>
+where
+    Word: Eq + Hash + 'a,
+    T: Sentence<Word>,
+    U: Sentence<Word>,
+{
+    let word = |(i, offset): (usize, usize)| -> &'a Word { &a[i].words()[offset] };
+    let mut entries: Vec<Entry> = vec![];
+    let mut merged: HashMap<(&Word, &Word), usize> = HashMap::new();
+    for (a_start, b_start, chapter) in chapters {
+        for mut entry in chapter {
+            entry.a.0 += a_start;
+            entry.b.0 += b_start;
+            let key = (word(entry.a), &b[entry.b.0].words()[entry.b.1]);
+            match merged.get(&key) {
+                Some(&i) => entries[i].count += entry.count,
+                None => {
+                    merged.insert(key, entries.len());
+                    entries.push(entry);
+                }
+            }
+        }
+    }
+
+    let mut totals: HashMap<&Word, usize> = HashMap::new();
+    let mut first: HashMap<&Word, (usize, usize)> = HashMap::new();
+    for entry in &entries {
+        *totals.entry(word(entry.a)).or_default() += entry.count;
+        first.entry(word(entry.a)).or_insert(entry.a);
+    }
+    for entry in &mut entries {
+        entry.probability = entry.count as f32 / totals[word(entry.a)] as f32;
+    }
+    entries.sort_by(|x, y| {
+        first[word(x.a)]
+            .cmp(&first[word(y.a)])
+            .then(y.probability.total_cmp(&x.probability))
+            .then(x.b.cmp(&y.b))
+    });
+
+    entries
+}
+
+/// Returns the sentence and offset of the occurrence `word` among the `sentences` containing it
+fn locate<Word, S, Axis>(
+    text: &[S],
+    sentences: impl Iterator<Item = Axis>,
+    word: &Word,
+) -> Option<(usize, usize)>
+where
+    Word: PartialEq,
+    S: Sentence<Word>,
+    Axis: Into<usize>,
+{
+    sentences.map(Into::into).find_map(|i| {
+        text[i]
+            .words()
+            .iter()
+            .position(|other| ptr::eq(other, word))
+            .map(|offset| (i, offset))
+    })
+}
+
+impl<'a, T, U> Output<'a, T, U> {
+    /// Returns the bilingual lexicon induced from the alignment: the pairs of words associated while
+    /// aligning, with the number of aligned pairs in which they occur together and the probability of each
+    /// translation of a word of text `a`, most probable first. Always empty unless the texts were aligned
+    /// with `Config::translations` enabled
+    pub fn translations<Word>(&self) -> Vec<Translation<'_, Word>>
+    where
+        Word: PartialEq,
+        T: Sentence<Word>,
+        U: Sentence<Word>,
+    {
+        self.translations
+            .iter()
+            .map(|entry| Translation {
+                a: &self.a[entry.a.0].words()[entry.a.1],
+                b: &self.b[entry.b.0].words()[entry.b.1],
+                count: entry.count,
+                probability: entry.probability,
+            })
+            .collect()
+    }
+
+    /// Writes the `translations` to `writer` with one translation per line as tab separated `a`, `b`,
+    /// `count` and `probability`
+    pub fn export_translations<Word>(&self, mut writer: impl Write) -> io::Result<()>
+    where
+        Word: PartialEq + Display,
+        T: Sentence<Word>,
+        U: Sentence<Word>,
+    {
+        for translation in self.translations() {
+            writeln!(
+                writer,
+                "{}\t{}\t{}\t{}",
+                translation.a, translation.b, translation.count, translation.probability
+            )?;
+        }
+
+        Ok(())
+    }
+}
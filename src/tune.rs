@@ -0,0 +1,444 @@
+//! Supervised search for the `Config` parameters best reproducing a gold alignment
+
+use std::collections::HashSet;
+use std::hash::Hash;
+use std::ops::Range;
+
+use super::{Config, Sentence};
+
+/// Numeric parameters of a `Config` explored by the `Tuner`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Parameters {
+    pub anchor_threshold: usize,
+    pub word_frequency_threshold: usize,
+    pub word_frequency_taper: usize,
+    pub word_frequency_minimum: usize,
+    pub word_similarity_threshold: f32,
+    pub word_similarity_taper: f32,
+    pub word_similarity_minimum: f32,
+    pub corridor_width: f32,
+}
+
+impl Parameters {
+    /// Returns `config` with these parameters applied
+    pub fn apply<Word>(self, config: Config<Word>) -> Config<Word> {
+        Config {
+            anchor_threshold: self.anchor_threshold,
+            word_frequency_threshold: self.word_frequency_threshold,
+            word_frequency_taper: self.word_frequency_taper,
+            word_frequency_minimum: self.word_frequency_minimum,
+            word_similarity_threshold: self.word_similarity_threshold,
+            word_similarity_taper: self.word_similarity_taper,
+            word_similarity_minimum: self.word_similarity_minimum,
+            corridor_width: self.corridor_width,
+            ..config
+        }
+    }
+}
+
+/// Candidate values for each of the tuned parameters
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParameterGrid {
+    pub anchor_threshold: Vec<usize>,
+    pub word_frequency_threshold: Vec<usize>,
+    pub word_frequency_taper: Vec<usize>,
+    pub word_frequency_minimum: Vec<usize>,
+    pub word_similarity_threshold: Vec<f32>,
+    pub word_similarity_taper: Vec<f32>,
+    pub word_similarity_minimum: Vec<f32>,
+    pub corridor_width: Vec<f32>,
+}
+
+impl Default for ParameterGrid {
+    fn default() -> Self {
+        Self {
+            anchor_threshold: vec![2, 3, 4],
+            word_frequency_threshold: vec![2, 3, 5],
+            word_frequency_taper: vec![0],
+            word_frequency_minimum: vec![0],
+            word_similarity_threshold: vec![0.6, 0.7, 0.8],
+            word_similarity_taper: vec![0.05],
+            word_similarity_minimum: vec![0.3],
+            corridor_width: vec![1.0, 1.5],
+        }
+    }
+}
+
+impl ParameterGrid {
+    /// Returns the number of parameter combinations in the grid
+    pub fn len(&self) -> usize {
+        self.anchor_threshold.len()
+            * self.word_frequency_threshold.len()
+            * self.word_frequency_taper.len()
+            * self.word_frequency_minimum.len()
+            * self.word_similarity_threshold.len()
+            * self.word_similarity_taper.len()
+            * self.word_similarity_minimum.len()
+            * self.corridor_width.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the `i`th parameter combination of the grid
+    pub fn get(&self, mut i: usize) -> Option<Parameters> {
+        if i >= self.len() {
+            return None;
+        }
+
+        let mut next = |len: usize| {
+            let j = i % len;
+            i /= len;
+            j
+        };
+
+        Some(Parameters {
+            anchor_threshold: self.anchor_threshold[next(self.anchor_threshold.len())],
+            word_frequency_threshold: self.word_frequency_threshold
+                [next(self.word_frequency_threshold.len())],
+            word_frequency_taper: self.word_frequency_taper[next(self.word_frequency_taper.len())],
+            word_frequency_minimum: self.word_frequency_minimum
+                [next(self.word_frequency_minimum.len())],
+            word_similarity_threshold: self.word_similarity_threshold
+                [next(self.word_similarity_threshold.len())],
+            word_similarity_taper: self.word_similarity_taper
+                [next(self.word_similarity_taper.len())],
+            word_similarity_minimum: self.word_similarity_minimum
+                [next(self.word_similarity_minimum.len())],
+            corridor_width: self.corridor_width[next(self.corridor_width.len())],
+        })
+    }
+}
+
+/// Strategy for exploring the `ParameterGrid`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Search {
+    /// Evaluate every combination of the grid
+    Grid,
+    /// Evaluate `samples` combinations of the grid chosen pseudo-randomly from `seed`
+    Random { samples: usize, seed: u64 },
+}
+
+/// Measure by which configurations are compared
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Objective {
+    /// Maximize the F1 score
+    F1,
+    /// Minimize the alignment error rate
+    Aer,
+}
+
+/// Quality of an alignment relative to a gold alignment
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Evaluation {
+    pub precision: f32,
+    pub recall: f32,
+    pub f1: f32,
+    /// Alignment error rate, treating every gold pair as a sure alignment
+    pub aer: f32,
+}
+
+impl Evaluation {
+    /// Evaluates the `predicted` pairs of sentence indices `(a, b)` against the `gold` pairs
+    pub fn new(
+        predicted: impl IntoIterator<Item = (usize, usize)>,
+        gold: &[(usize, usize)],
+    ) -> Self {
+        let gold = gold.iter().copied().collect::<HashSet<_>>();
+        let predicted = predicted.into_iter().collect::<HashSet<_>>();
+        let correct = predicted.intersection(&gold).count() as f32;
+
+        let precision = if predicted.is_empty() {
+            0.0
+        } else {
+            correct / predicted.len() as f32
+        };
+        let recall = if gold.is_empty() {
+            0.0
+        } else {
+            correct / gold.len() as f32
+        };
+        let f1 = if precision + recall == 0.0 {
+            0.0
+        } else {
+            2.0 * precision * recall / (precision + recall)
+        };
+        let aer = if predicted.len() + gold.len() == 0 {
+            0.0
+        } else {
+            1.0 - 2.0 * correct / (predicted.len() + gold.len()) as f32
+        };
+
+        Self {
+            precision,
+            recall,
+            f1,
+            aer,
+        }
+    }
+
+    /// Returns the mean of the `evaluations`
+    fn mean(evaluations: &[Self]) -> Self {
+        let n = evaluations.len().max(1) as f32;
+        let mean = |measure: fn(&Self) -> f32| evaluations.iter().map(measure).sum::<f32>() / n;
+
+        Self {
+            precision: mean(|evaluation| evaluation.precision),
+            recall: mean(|evaluation| evaluation.recall),
+            f1: mean(|evaluation| evaluation.f1),
+            aer: mean(|evaluation| evaluation.aer),
+        }
+    }
+
+    fn score(&self, objective: Objective) -> f32 {
+        match objective {
+            Objective::F1 => self.f1,
+            Objective::Aer => -self.aer,
+        }
+    }
+}
+
+/// Result of tuning: the best parameters found and their evaluation
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tuned {
+    pub parameters: Parameters,
+    pub evaluation: Evaluation,
+}
+
+pub(crate) type ConfigFactory<Word> = Box<dyn Fn() -> Config<Word>>;
+
+pub struct Tuner<Word> {
+    /// Provides the configuration to which each parameter combination is applied. Defaults to `Config::default`
+    pub config: ConfigFactory<Word>,
+    /// Candidate parameter values. Defaults to `ParameterGrid::default()`
+    pub grid: ParameterGrid,
+    /// Defaults to `Search::Grid`
+    pub search: Search,
+    /// Defaults to `Objective::F1`
+    pub objective: Objective,
+}
+
+impl<Word> Default for Tuner<Word> {
+    fn default() -> Self {
+        Self {
+            // The closure avoids requiring `Word: 'static`, which boxing `Config::default` would
+            #[allow(clippy::redundant_closure)]
+            config: Box::new(|| Config::default()),
+            grid: ParameterGrid::default(),
+            search: Search::Grid,
+            objective: Objective::F1,
+        }
+    }
+}
+
+impl<Word> Tuner<Word> {
+    /// Aligns `a` and `b` with each explored parameter combination and returns the combination
+    /// best reproducing the `gold` pairs of sentence indices `(a, b)`
+    pub fn tune<T, U>(&self, a: &[T], b: &[U], gold: &[(usize, usize)]) -> Option<Tuned>
+    where
+        Word: Eq + Hash,
+        T: Sentence<Word>,
+        U: Sentence<Word>,
+    {
+        let candidates: Box<dyn Iterator<Item = usize>> = match self.search {
+            Search::Grid => Box::new(0..self.grid.len()),
+            Search::Random { samples, mut seed } => {
+                let len = self.grid.len().max(1) as u64;
+                Box::new((0..samples).map(move |_| {
+                    // xorshift64*
+                    seed ^= seed >> 12;
+                    seed ^= seed << 25;
+                    seed ^= seed >> 27;
+                    (seed.wrapping_mul(0x2545_f491_4f6c_dd1d) % len) as usize
+                }))
+            }
+        };
+
+        let mut best: Option<Tuned> = None;
+        for parameters in candidates.filter_map(|i| self.grid.get(i)) {
+            let output = parameters.apply((self.config)()).align(a, b);
+            let evaluation = Evaluation::new(output.pairs().map(|pair| pair.indices()), gold);
+
+            if best.is_none_or(|best| {
+                evaluation.score(self.objective) > best.evaluation.score(self.objective)
+            }) {
+                best = Some(Tuned {
+                    parameters,
+                    evaluation,
+                });
+            }
+        }
+
+        best
+    }
+}
+
+/// A contiguous section of a gold-aligned pair of texts, tuned on independently of the others
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fold {
+    /// Range of the sentences of the fold in text `a`
+    pub a: Range<usize>,
+    /// Range of the sentences of the fold in text `b`
+    pub b: Range<usize>,
+    /// Best parameters found for the fold and their evaluation on it
+    pub tuned: Tuned,
+    /// Mean evaluation of the parameters of the fold on the other folds
+    pub held_out: Evaluation,
+}
+
+/// Result of cross-validation: the parameters tuned on each fold and how well they carry over
+#[derive(Debug, Clone, PartialEq)]
+pub struct CrossValidation {
+    pub folds: Vec<Fold>,
+    /// Parameters chosen by the most folds
+    pub consensus: Parameters,
+    /// Fraction of the folds which chose the `consensus`. Low values indicate parameters fitted to the
+    /// particulars of each section rather than to the texts
+    pub stability: f32,
+    /// Mean of the held out evaluations of the folds, the performance to expect on unseen texts
+    pub generalization: Evaluation,
+}
+
+impl<Word> Tuner<Word> {
+    /// Splits `a` and `b` into `folds` contiguous sections holding equal numbers of the `gold` pairs of
+    /// sentence indices `(a, b)`, tunes on each fold and evaluates the parameters found on the other folds.
+    /// A section which would not start after the start of the previous one in both texts is merged into
+    /// it, and sections without gold pairs are left out. Returns `None` unless there are at least two
+    /// folds with a gold pair each
+    pub fn cross_validate<T, U>(
+        &self,
+        a: &[T],
+        b: &[U],
+        gold: &[(usize, usize)],
+        folds: usize,
+    ) -> Option<CrossValidation>
+    where
+        Word: Eq + Hash,
+        T: Sentence<Word>,
+        U: Sentence<Word>,
+    {
+        let mut gold = gold
+            .iter()
+            .copied()
+            .filter(|&(i, j)| i < a.len() && j < b.len())
+            .collect::<Vec<_>>();
+        gold.sort_unstable();
+        if folds < 2 || gold.len() < folds {
+            return None;
+        }
+
+        // Each fold starts at the first gold pair of its share following the start of the previous fold in
+        // both texts, so that no section is empty, and ends where the next fold starts
+        let mut starts = vec![(0, 0)];
+        for k in 1..folds {
+            let &(a_previous, b_previous) = starts.last().expect("the first fold starts at 0");
+            starts.extend(
+                gold[k * gold.len() / folds..]
+                    .iter()
+                    .find(|&&(i, j)| i > a_previous && j > b_previous),
+            );
+        }
+        let sections = (0..starts.len())
+            .map(|k| {
+                let (a_start, b_start) = starts[k];
+                let (a_end, b_end) = starts.get(k + 1).copied().unwrap_or((a.len(), b.len()));
+                let (a_range, b_range) = (a_start..a_end, b_start..b_end);
+                let section_gold = gold
+                    .iter()
+                    .filter(|(i, j)| a_range.contains(i) && b_range.contains(j))
+                    .map(|(i, j)| (i - a_range.start, j - b_range.start))
+                    .collect::<Vec<_>>();
+
+                (a_range, b_range, section_gold)
+            })
+            .filter(|(.., section_gold)| !section_gold.is_empty())
+            .collect::<Vec<_>>();
+        if sections.len() < 2 {
+            return None;
+        }
+
+        let mut results = vec![];
+        for (a_range, b_range, section_gold) in &sections {
+            let tuned = self.tune(&a[a_range.clone()], &b[b_range.clone()], section_gold)?;
+            let held_out = sections
+                .iter()
+                .filter(|(other, ..)| other != a_range)
+                .map(|(a_range, b_range, section_gold)| {
+                    let output = tuned
+                        .parameters
+                        .apply((self.config)())
+                        .align(&a[a_range.clone()], &b[b_range.clone()]);
+                    Evaluation::new(output.pairs().map(|pair| pair.indices()), section_gold)
+                })
+                .collect::<Vec<_>>();
+
+            results.push(Fold {
+                a: a_range.clone(),
+                b: b_range.clone(),
+                tuned,
+                held_out: Evaluation::mean(&held_out),
+            });
+        }
+
+        let (consensus, agreeing) = results
+            .iter()
+            .map(|fold| {
+                let parameters = fold.tuned.parameters;
+                let agreeing = results
+                    .iter()
+                    .filter(|other| other.tuned.parameters == parameters)
+                    .count();
+                (parameters, agreeing)
+            })
+            .fold(
+                None,
+                |best: Option<(Parameters, usize)>, candidate| match best {
+                    Some(best) if best.1 >= candidate.1 => Some(best),
+                    _ => Some(candidate),
+                },
+            )?;
+        let generalization =
+            Evaluation::mean(&results.iter().map(|fold| fold.held_out).collect::<Vec<_>>());
+
+        Some(CrossValidation {
+            stability: agreeing as f32 / results.len() as f32,
+            folds: results,
+            consensus,
+            generalization,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ParameterGrid, Tuner};
+
+    #[test]
+    fn cross_validation_merges_empty_sections() {
+        let a = [vec!["one", "two"], vec!["three", "four"]];
+        let b = [vec!["uno"], vec!["dos"], vec!["tres"], vec!["cuatro"]];
+        let a = a.iter().map(Vec::as_slice).collect::<Vec<_>>();
+        let b = b.iter().map(Vec::as_slice).collect::<Vec<_>>();
+        let tuner = Tuner {
+            grid: ParameterGrid {
+                anchor_threshold: vec![2],
+                word_frequency_threshold: vec![2],
+                word_similarity_threshold: vec![0.8],
+                corridor_width: vec![1.0],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let cross_validation = tuner
+            .cross_validate(&a, &b, &[(0, 0), (0, 1), (1, 2), (1, 3)], 4)
+            .expect("two sections hold gold pairs");
+        let sections = cross_validation
+            .folds
+            .iter()
+            .map(|fold| (fold.a.clone(), fold.b.clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(sections, [(0..1, 0..2), (1..2, 2..4)]);
+    }
+}
@@ -0,0 +1,114 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::io::{self, Write};
+
+use super::{Coordinates, Output, Sentence, X, Y};
+
+/// A candidate alignment whose status a human annotator could most usefully decide
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UncertainPair {
+    /// Index of the sentence in text `a`
+    pub a: usize,
+    /// Index of the sentence in text `b`
+    pub b: usize,
+    /// Score of the pair in the SAT
+    pub score: usize,
+    /// Number of other scored pairs sharing a sentence with this pair
+    pub conflicts: usize,
+    /// Uncertainty in `[0, 1]`. For pairs below the anchor threshold this is their score relative to the
+    /// threshold, for anchors it is the best conflicting score relative to their own
+    pub uncertainty: f32,
+}
+
+impl<'a, T, U> Output<'a, T, U> {
+    /// Returns the pairs which are either scored below the anchor threshold or anchors with conflicting
+    /// candidates, ordered from most to least uncertain
+    pub fn uncertain(&self) -> Vec<UncertainPair> {
+        let threshold = self.sat.anchor_threshold().0 as f32;
+        let mut a_scores: HashMap<Y, Vec<(X, usize)>> = HashMap::new();
+        let mut b_scores: HashMap<X, Vec<(Y, usize)>> = HashMap::new();
+        for (Coordinates(x, y), score) in self.sat.cells() {
+            a_scores.entry(y).or_default().push((x, score.0));
+            b_scores.entry(x).or_default().push((y, score.0));
+        }
+
+        let mut uncertain = self
+            .sat
+            .cells()
+            .filter_map(|(Coordinates(x, y), score)| {
+                let competitors = a_scores[&y]
+                    .iter()
+                    .filter(|(other, _)| *other != x)
+                    .map(|(_, score)| *score)
+                    .chain(
+                        b_scores[&x]
+                            .iter()
+                            .filter(|(other, _)| *other != y)
+                            .map(|(_, score)| *score),
+                    )
+                    .collect::<Vec<_>>();
+
+                let uncertainty = if score < self.sat.anchor_threshold() {
+                    score.0 as f32 / threshold
+                } else {
+                    let best = competitors.iter().copied().max()?;
+                    best.min(score.0) as f32 / score.0 as f32
+                };
+
+                Some(UncertainPair {
+                    a: y.0,
+                    b: x.0,
+                    score: score.0,
+                    conflicts: competitors.len(),
+                    uncertainty,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        uncertain.sort_by(|a, b| {
+            b.uncertainty
+                .total_cmp(&a.uncertainty)
+                .then(b.conflicts.cmp(&a.conflicts))
+                .then((a.a, a.b).cmp(&(b.a, b.b)))
+        });
+
+        uncertain
+    }
+
+    /// Writes up to `limit` of the most uncertain pairs to `writer` as tab separated values for annotation,
+    /// with columns `a`, `b`, `score`, `conflicts`, `uncertainty`, followed by the text of both sentences
+    pub fn export_uncertain<Word>(&self, mut writer: impl Write, limit: usize) -> io::Result<()>
+    where
+        Word: PartialEq + Display,
+        T: Sentence<Word>,
+        U: Sentence<Word>,
+    {
+        writeln!(
+            writer,
+            "a\tb\tscore\tconflicts\tuncertainty\ta_text\tb_text"
+        )?;
+        for pair in self.uncertain().into_iter().take(limit) {
+            writeln!(
+                writer,
+                "{}\t{}\t{}\t{}\t{}\t{}\t{}",
+                pair.a,
+                pair.b,
+                pair.score,
+                pair.conflicts,
+                pair.uncertainty,
+                text(self.a[pair.a].words()),
+                text(self.b[pair.b].words()),
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+fn text<Word: Display>(words: &[Word]) -> String {
+    words
+        .iter()
+        .map(|word| word.to_string().replace(['\t', '\n'], " "))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
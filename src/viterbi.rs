@@ -0,0 +1,144 @@
+use super::hash::HashSet;
+use super::{Coordinates, SentenceAlignmentTable, X, Y};
+
+/// Decoding of the anchors as the most probable monotone path through the SAT, rather than thresholding
+/// the scores of its cells. Each cell is taken as log-odds evidence of its sentences corresponding,
+/// `ln(score / anchor_threshold)`, so that a cell at the anchor threshold is neutral, and each sentence
+/// the path leaves unaligned costs the `gap_log_probability`. Priors over the cells add to their evidence.
+/// The path is found by Viterbi decoding over the cells in order of text `a`, keeping only the most
+/// probable partial paths, and may align a sentence to several consecutive sentences of the other text
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Viterbi {
+    /// Log probability of a sentence of either text being left unaligned. The lower it is, the weaker the
+    /// cells the path takes to align the sentences around them. Defaults to `-0.25`
+    pub gap_log_probability: f32,
+    /// Weight of the prior favoring the cells near the diagonal of the texts, subtracted from the evidence
+    /// of a cell in proportion to its distance from the diagonal relative to the lengths of the texts.
+    /// Defaults to `0.0`
+    pub diagonal_prior: f32,
+    /// Number of partial paths kept as the predecessors of the cells still to be decoded. Defaults to `64`
+    pub beam_width: usize,
+}
+
+impl Default for Viterbi {
+    fn default() -> Self {
+        Self {
+            gap_log_probability: -0.25,
+            diagonal_prior: 0.0,
+            beam_width: 64,
+        }
+    }
+}
+
+/// Partial path ending at a cell
+struct State {
+    coordinates: Coordinates,
+    log_probability: f32,
+    previous: Option<usize>,
+}
+
+impl Viterbi {
+    /// Replaces the anchors of the SAT, other than the locked anchors, with the cells of the most probable
+    /// path through the texts of `a_len` and `b_len` sentences, returning the cells which became anchors
+    pub(crate) fn decode(
+        &self,
+        sat: &mut SentenceAlignmentTable,
+        a_len: usize,
+        b_len: usize,
+    ) -> Vec<Coordinates> {
+        let threshold = sat.anchor_threshold().0.max(1) as f32;
+        let mut cells = sat
+            .cells()
+            .filter(|&(coordinates, _)| !sat.crosses_locked(coordinates))
+            .map(|(coordinates @ Coordinates(X(x), Y(y)), score)| {
+                let distance = (x as f32 / b_len as f32 - y as f32 / a_len as f32).abs();
+                let evidence = (score.0 as f32 / threshold).ln() - self.diagonal_prior * distance;
+                (coordinates, evidence)
+            })
+            .collect::<Vec<_>>();
+        cells.sort_unstable_by_key(|&(Coordinates(x, y), _)| (y, x));
+
+        // Number of sentences left unaligned between the end of a path and a cell, or the end of the texts
+        let gaps = |from: Option<Coordinates>, Coordinates(X(x), Y(y)): Coordinates| match from {
+            Some(Coordinates(X(from_x), Y(from_y))) => {
+                (x - from_x).saturating_sub(1) + (y - from_y).saturating_sub(1)
+            }
+            None => x + y,
+        };
+        let end = Coordinates(X(b_len), Y(a_len));
+        let gap = self.gap_log_probability;
+        let completed =
+            |state: &State| state.log_probability + gap * gaps(Some(state.coordinates), end) as f32;
+
+        let mut states: Vec<State> = vec![];
+        let mut beam: Vec<usize> = vec![];
+        for (coordinates @ Coordinates(x, y), evidence) in cells {
+            let (previous, log_probability) = beam
+                .iter()
+                .filter(|&&i| {
+                    let Coordinates(from_x, from_y) = states[i].coordinates;
+                    from_x <= x && from_y <= y
+                })
+                .map(|&i| {
+                    let from = &states[i];
+                    let gaps = gaps(Some(from.coordinates), coordinates);
+                    (Some(i), from.log_probability + gap * gaps as f32)
+                })
+                .fold(
+                    (None, gap * gaps(None, coordinates) as f32),
+                    |best, next| {
+                        if next.1 > best.1 {
+                            next
+                        } else {
+                            best
+                        }
+                    },
+                );
+
+            beam.push(states.len());
+            states.push(State {
+                coordinates,
+                log_probability: log_probability + evidence,
+                previous,
+            });
+            if beam.len() >= 2 * self.beam_width.max(1) {
+                beam.sort_unstable_by(|&i, &j| {
+                    completed(&states[j]).total_cmp(&completed(&states[i]))
+                });
+                beam.truncate(self.beam_width.max(1));
+            }
+        }
+
+        // The empty path leaves every sentence unaligned
+        let mut best = None;
+        let mut best_log_probability = gap * gaps(None, end) as f32;
+        for (i, state) in states.iter().enumerate() {
+            if completed(state) > best_log_probability {
+                best = Some(i);
+                best_log_probability = completed(state);
+            }
+        }
+
+        let mut path = vec![];
+        while let Some(i) = best {
+            path.push(states[i].coordinates);
+            best = states[i].previous;
+        }
+
+        let on_path = path.iter().copied().collect::<HashSet<_>>();
+        for anchor in sat.anchors().collect::<Vec<_>>() {
+            if !on_path.contains(&anchor) {
+                sat.remove(anchor);
+            }
+        }
+        let mut anchored = vec![];
+        for coordinates in path.into_iter().rev() {
+            if !sat.is_anchor(coordinates) {
+                sat.anchor(coordinates);
+                anchored.push(coordinates);
+            }
+        }
+
+        anchored
+    }
+}
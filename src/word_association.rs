@@ -1,18 +1,49 @@
 use std::cmp::{Eq, Ord, Ordering, PartialEq, PartialOrd};
-use std::collections::{HashMap, HashSet};
 use std::fmt::Debug;
 use std::hash::Hash;
 
+use super::hash::{HashMap, HashSet};
 use super::{
-    AlignableSentenceTable, Coordinates, Score, SentenceAlignmentTable, WordSentenceIndex, X, Y,
+    AlignableSentenceTable, CandidateAnchor, Coordinates, Ensemble, Reranker, Score,
+    SentenceAlignmentTable, WordSentenceIndex, X, Y,
 };
 
+/// Similarity in `[0, 1]` scaled to an integer, so that the ordering of associations is exact and
+/// independent of the platform
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub(crate) struct Similarity(pub(crate) u32);
+
+impl Similarity {
+    const SCALE: u32 = 1_000_000;
+    pub(crate) const ONE: Self = Self(Self::SCALE);
+
+    /// Returns the similarity `numerator / denominator`, rounded to the nearest step of the scale
+    fn ratio(numerator: usize, denominator: usize) -> Self {
+        let scaled = numerator as u64 * u64::from(Self::SCALE);
+        let denominator = denominator.max(1) as u64;
+        Self(((scaled + denominator / 2) / denominator) as u32)
+    }
+
+    pub(crate) fn to_f32(self) -> f32 {
+        self.0 as f32 / Self::SCALE as f32
+    }
+}
+
+impl From<f32> for Similarity {
+    fn from(similarity: f32) -> Self {
+        Self((similarity.max(0.0) * Self::SCALE as f32).round() as u32)
+    }
+}
+
 pub struct WordAssociation<'a, Word> {
     pub a: &'a Word,
     pub b: &'a Word,
-    pub similarity: f32,
+    pub(crate) similarity: Similarity,
     pub a_occurrences: usize,
     pub b_occurrences: usize,
+    /// Rank of the association in the `TieBreak` order, used to break ties between associations of equal
+    /// similarity. Initially the order in which the association was discovered
+    pub order: usize,
     ast: &'a AlignableSentenceTable,
     a_word_sentence_index: &'a WordSentenceIndex<'a, Word, Y>,
     b_word_sentence_index: &'a WordSentenceIndex<'a, Word, X>,
@@ -20,16 +51,7 @@ pub struct WordAssociation<'a, Word> {
 
 impl<'a, Word> Clone for WordAssociation<'a, Word> {
     fn clone(&self) -> Self {
-        Self {
-            a: self.a,
-            b: self.b,
-            similarity: self.similarity,
-            a_occurrences: self.a_occurrences,
-            b_occurrences: self.b_occurrences,
-            ast: self.ast,
-            a_word_sentence_index: self.a_word_sentence_index,
-            b_word_sentence_index: self.b_word_sentence_index,
-        }
+        *self
     }
 }
 
@@ -43,29 +65,58 @@ where
         f.debug_struct("WordAssociation")
             .field("a", &self.a)
             .field("b", &self.b)
-            .field("similarity", &self.similarity)
+            .field("similarity", &self.similarity.to_f32())
             .field("b_freq", &self.b_occurrences)
             .finish()
     }
 }
 
-impl<'a, Word: Eq + Hash + Debug> WordAssociation<'a, Word> {
+impl<'a, Word: Eq + Hash> WordAssociation<'a, Word> {
+    /// Creates the association of `a` and `b`, with their co-occurrence similarity if already `computed`
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn new(
         ast: &'a AlignableSentenceTable,
         a_word_sentence_index: &'a WordSentenceIndex<'a, Word, Y>,
         b_word_sentence_index: &'a WordSentenceIndex<'a, Word, X>,
         a: &'a Word,
         b: &'a Word,
-        association_mapper: impl for<'b> Fn(&'b Word, &'b Word) -> bool,
+        order: usize,
+        prior: Option<f32>,
+        computed: Option<Similarity>,
+        ensemble: Option<&Ensemble<Word>>,
     ) -> Self {
-        let mapped_association = association_mapper(a, b);
+        let mapped_association = prior.is_some_and(|similarity| similarity >= 1.0);
         Self {
             a,
             b,
+            order,
             similarity: if mapped_association {
-                1.0
+                Similarity::ONE
             } else {
-                Self::similarity(ast, a_word_sentence_index, b_word_sentence_index, a, b)
+                let cooccurrence = || {
+                    computed.unwrap_or_else(|| {
+                        Self::similarity(ast, a_word_sentence_index, b_word_sentence_index, a, b)
+                    })
+                };
+                let similarity = match ensemble {
+                    Some(ensemble) => Similarity::from(ensemble.similarity(
+                        a,
+                        b,
+                        || cooccurrence().to_f32(),
+                        || {
+                            1.0 - (a_word_sentence_index.mean_position(a)
+                                - b_word_sentence_index.mean_position(b))
+                            .abs()
+                        },
+                        || {
+                            1.0 - (a_word_sentence_index.mean_offset(a)
+                                - b_word_sentence_index.mean_offset(b))
+                            .abs()
+                        },
+                    )),
+                    None => cooccurrence(),
+                };
+                prior.map_or(similarity, |prior| similarity.max(Similarity::from(prior)))
             },
             a_occurrences: if mapped_association {
                 usize::MAX
@@ -83,52 +134,123 @@ impl<'a, Word: Eq + Hash + Debug> WordAssociation<'a, Word> {
         }
     }
 
-    pub(crate) fn align_sentences(&self, sat: &mut SentenceAlignmentTable) -> Vec<Coordinates> {
-        let mut a_candidates = HashMap::new();
-        let mut b_candidates = HashMap::new();
+    /// Returns the cells of the corridor supported by the association, which are the unique candidates or,
+    /// with `positional_disambiguation`, the positional matches. Forbidden cells and cells crossing a
+    /// locked anchor are excluded
+    pub(crate) fn matches(
+        &self,
+        sat: &SentenceAlignmentTable,
+        positional_disambiguation: bool,
+    ) -> Vec<Coordinates> {
+        let mut a_candidates = HashMap::default();
+        let mut b_candidates = HashMap::default();
         for y in self.a_word_sentence_index.sentences(self.a) {
             for x in self.b_word_sentence_index.sentences(self.b) {
                 if self.ast.contains(Coordinates(x, y)) {
-                    a_candidates.entry(y).or_insert_with(HashSet::new).insert(x);
-                    b_candidates.entry(x).or_insert_with(HashSet::new).insert(y);
+                    a_candidates
+                        .entry(y)
+                        .or_insert_with(HashSet::default)
+                        .insert(x);
+                    b_candidates
+                        .entry(x)
+                        .or_insert_with(HashSet::default)
+                        .insert(y);
                 }
             }
         }
 
-        let matches = b_candidates
-            .into_iter()
-            .filter(|(x, ys)| {
-                ys.len() == 1
-                    && a_candidates[ys.iter().next().unwrap()].len() == 1
-                    && a_candidates[ys.iter().next().unwrap()]
-                        .iter()
-                        .next()
-                        .unwrap()
-                        == x
-            })
-            .map(|(x, ys)| Coordinates(x, *ys.iter().next().unwrap()))
-            .collect::<Vec<_>>();
+        let mut matches = if positional_disambiguation {
+            positional_matches(&a_candidates, &b_candidates)
+        } else {
+            b_candidates
+                .into_iter()
+                .filter(|(x, ys)| {
+                    ys.len() == 1
+                        && a_candidates[ys.iter().next().unwrap()].len() == 1
+                        && a_candidates[ys.iter().next().unwrap()]
+                            .iter()
+                            .next()
+                            .unwrap()
+                            == x
+                })
+                .map(|(x, ys)| Coordinates(x, *ys.iter().next().unwrap()))
+                .collect::<Vec<_>>()
+        };
+        matches.retain(|&coordinates| {
+            !sat.is_forbidden(coordinates) && !sat.crosses_locked(coordinates)
+        });
+
+        matches
+    }
+
+    pub(crate) fn align_sentences(
+        &self,
+        sat: &mut SentenceAlignmentTable,
+        reranker: Option<&Reranker<Word>>,
+        positional_disambiguation: bool,
+    ) -> Result<Vec<Coordinates>, Coordinates> {
+        let matches = self.proposals(sat, reranker, positional_disambiguation)?;
+        for &coordinates in &matches {
+            sat.increment(coordinates);
+        }
+
+        Ok(matches)
+    }
+
+    /// Returns the cells to which the association would contribute evidence, vetted by the `reranker`, or
+    /// a cell which would cross the existing evidence of the `sat`, rejecting the association
+    pub(crate) fn proposals(
+        &self,
+        sat: &SentenceAlignmentTable,
+        reranker: Option<&Reranker<Word>>,
+        positional_disambiguation: bool,
+    ) -> Result<Vec<Coordinates>, Coordinates> {
+        let mut matches = self.matches(sat, positional_disambiguation);
 
         for &coordinate in &matches {
             if sat.score(coordinate) == Score::ZERO && sat.crossover(coordinate) {
-                return vec![];
+                return Err(coordinate);
             }
         }
 
-        for &coordinates in &matches {
-            sat.increment(coordinates);
+        if let Some(reranker) = reranker {
+            let (anchors, mut others): (Vec<_>, Vec<_>) = matches
+                .into_iter()
+                .partition(|&coordinates| sat.completes_anchor(coordinates));
+            let proposed = anchors.iter().copied().collect::<HashSet<_>>();
+            let mut candidates = anchors
+                .into_iter()
+                .map(|coordinates @ Coordinates(x, y)| CandidateAnchor {
+                    a: y.0,
+                    b: x.0,
+                    a_word: self.a,
+                    b_word: self.b,
+                    similarity: self.similarity.to_f32(),
+                    score: sat.score(coordinates).0 + 1,
+                })
+                .collect();
+            reranker(&mut candidates);
+
+            others.extend(
+                candidates
+                    .into_iter()
+                    .map(|candidate| Coordinates(X(candidate.b), Y(candidate.a)))
+                    .filter(|coordinates| proposed.contains(coordinates)),
+            );
+            matches = others;
         }
 
-        matches
+        Ok(matches)
     }
 
-    fn similarity(
+    /// Returns the similarity of the occurrences of `a` and `b` in the alignable cells
+    pub(crate) fn similarity(
         ast: &AlignableSentenceTable,
         a_word_sentence_index: &WordSentenceIndex<'a, Word, Y>,
         b_word_sentence_index: &WordSentenceIndex<'a, Word, X>,
         a: &Word,
         b: &Word,
-    ) -> f32 {
+    ) -> Similarity {
         let a_candidates = a_word_sentence_index
             .sentences(a)
             .map(|sentence| Candidate { sentence, ast })
@@ -149,7 +271,7 @@ impl<'a, Word: Eq + Hash + Debug> WordAssociation<'a, Word> {
         let a_occurrences = a_word_sentence_index.occurrences(a);
         let b_occurrences = b_word_sentence_index.occurrences(b);
 
-        (2 * c) as f32 / (a_occurrences + b_occurrences) as f32
+        Similarity::ratio((2 * c) as usize, a_occurrences + b_occurrences)
     }
 }
 
@@ -161,28 +283,68 @@ impl<'a, Word: PartialEq> PartialEq for WordAssociation<'a, Word> {
     }
 }
 
-impl<'a, Word: PartialEq + PartialOrd> PartialOrd for WordAssociation<'a, Word> {
+impl<'a, Word: PartialEq> PartialOrd for WordAssociation<'a, Word> {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'a, Word: PartialEq> Ord for WordAssociation<'a, Word> {
+    fn cmp(&self, other: &Self) -> Ordering {
         if self == other {
-            Some(Ordering::Equal)
+            Ordering::Equal
         } else {
-            match self.similarity.partial_cmp(&other.similarity) {
-                Some(Ordering::Equal) => match (self.a_occurrences + self.b_occurrences)
-                    .partial_cmp(&(other.a_occurrences + other.b_occurrences))
-                {
-                    Some(Ordering::Equal) => self.a.partial_cmp(other.a),
-                    ordering => ordering,
-                },
-                ordering => ordering,
-            }
+            // Earlier ranks take precedence, so they must sort higher
+            self.similarity
+                .cmp(&other.similarity)
+                .then(other.order.cmp(&self.order))
         }
     }
 }
 
-impl<'a, Word: PartialEq + PartialOrd> Ord for WordAssociation<'a, Word> {
-    fn cmp(&self, other: &Self) -> Ordering {
-        self.partial_cmp(other).unwrap_or(Ordering::Equal)
+/// Pairs the sentences of each connected group of candidates by their relative position, provided the
+/// group has as many sentences in text `a` as in text `b` and each positional pair is itself a candidate.
+/// Groups of a single pair are the unique candidates matched without disambiguation
+fn positional_matches(
+    a_candidates: &HashMap<Y, HashSet<X>>,
+    b_candidates: &HashMap<X, HashSet<Y>>,
+) -> Vec<Coordinates> {
+    let mut a_visited: HashSet<_> = HashSet::default();
+    let mut b_visited: HashSet<_> = HashSet::default();
+    let mut matches = vec![];
+
+    for &start in a_candidates.keys() {
+        if !a_visited.insert(start) {
+            continue;
+        }
+
+        let mut ys = vec![start];
+        let mut xs = vec![];
+        let mut stack = vec![start];
+        while let Some(y) = stack.pop() {
+            for &x in &a_candidates[&y] {
+                if b_visited.insert(x) {
+                    xs.push(x);
+                    for &y in &b_candidates[&x] {
+                        if a_visited.insert(y) {
+                            ys.push(y);
+                            stack.push(y);
+                        }
+                    }
+                }
+            }
+        }
+
+        if ys.len() == xs.len() {
+            ys.sort_unstable();
+            xs.sort_unstable();
+            if xs.iter().zip(&ys).all(|(x, y)| a_candidates[y].contains(x)) {
+                matches.extend(xs.into_iter().zip(ys).map(|(x, y)| Coordinates(x, y)));
+            }
+        }
     }
+
+    matches
 }
 
 struct Candidate<'a, Sentence> {
@@ -1,6 +1,24 @@
 use std::cmp::Reverse;
-use std::collections::BTreeSet;
+use std::collections::{BTreeSet, BinaryHeap};
 
 use super::WordAssociation;
 
 pub type WordAssociationTable<'a, Word> = BTreeSet<Reverse<WordAssociation<'a, Word>>>;
+
+/// Returns the table of the `k` highest ranked of the `associations`, holding no more than `k + 1` of them
+/// at once rather than ordering them all
+pub(crate) fn top<'a, Word: PartialEq>(
+    associations: impl IntoIterator<Item = WordAssociation<'a, Word>>,
+    k: usize,
+) -> WordAssociationTable<'a, Word> {
+    // The lowest ranked association kept is at the top of the heap, to be evicted by a higher one
+    let mut heap = BinaryHeap::with_capacity(k.saturating_add(1).min(1 << 16));
+    for association in associations {
+        heap.push(Reverse(association));
+        if heap.len() > k {
+            heap.pop();
+        }
+    }
+
+    heap.into_iter().collect()
+}
@@ -1,30 +1,238 @@
-use std::collections::HashMap;
 use std::hash::Hash;
+use std::mem::size_of;
 
-pub struct WordSentenceIndex<'a, Word, Axis>(HashMap<&'a Word, Vec<Axis>>);
+use super::hash::HashMap;
+use super::{Normalizer, OcrTolerance, WordFilter};
+
+pub struct WordSentenceIndex<'a, Word, Axis> {
+    postings: HashMap<&'a Word, Vec<Axis>>,
+    canonical: HashMap<&'a Word, &'a Word>,
+    len: usize,
+    /// Offsets of the occurrences of each word within their sentences, if recorded
+    positions: Option<HashMap<&'a Word, Vec<(Axis, usize)>>>,
+    /// Number of words of each sentence, if positions are recorded
+    lengths: Vec<usize>,
+    /// Number of words of each sentence indexed, i.e. not excluded by the filter
+    indexed_lengths: Vec<usize>,
+}
 
 impl<'a, Word, Axis: From<usize>> WordSentenceIndex<'a, Word, Axis>
 where
     Word: Eq + Hash,
-    Axis: Clone + Copy + From<usize>,
+    Axis: Clone + Copy + Ord + From<usize> + Into<usize>,
 {
-    pub fn new(text: impl Iterator<Item = &'a [Word]>) -> Self {
-        let mut map: HashMap<&Word, Vec<Axis>> = HashMap::new();
+    pub fn new(
+        text: impl Iterator<Item = &'a [Word]>,
+        normalizer: Option<&Normalizer<Word>>,
+        ocr_tolerance: &OcrTolerance,
+        filter: Option<&WordFilter<Word>>,
+        record_positions: bool,
+    ) -> Self {
+        let mut postings: HashMap<&Word, Vec<Axis>> = HashMap::default();
+        let mut canonical = HashMap::default();
+        let mut classes: HashMap<String, &Word> = HashMap::default();
+        let mut len = 0;
+        let mut positions: Option<HashMap<&Word, Vec<(Axis, usize)>>> =
+            record_positions.then(HashMap::default);
+        let mut lengths = vec![];
+        let mut indexed_lengths = vec![];
 
         for (i, sentence) in text.enumerate() {
-            for word in sentence {
-                map.entry(word).or_default().push(Axis::from(i));
+            len += 1;
+            if record_positions {
+                lengths.push(sentence.len());
+            }
+            indexed_lengths.push(0);
+            for (offset, word) in sentence.iter().enumerate() {
+                if filter.is_some_and(|filter| !filter(word)) {
+                    continue;
+                }
+                indexed_lengths[i] += 1;
+
+                let word = match normalizer {
+                    Some(normalizer) => *canonical.entry(word).or_insert_with(|| {
+                        *classes
+                            .entry(ocr_tolerance.canonicalize(normalizer(word)))
+                            .or_insert(word)
+                    }),
+                    None => word,
+                };
+
+                postings.entry(word).or_default().push(Axis::from(i));
+                if let Some(positions) = &mut positions {
+                    positions
+                        .entry(word)
+                        .or_default()
+                        .push((Axis::from(i), offset));
+                }
             }
         }
 
-        Self(map)
+        let mut index = Self {
+            postings,
+            canonical,
+            len,
+            positions,
+            lengths,
+            indexed_lengths,
+        };
+        if ocr_tolerance.max_edit_distance > 0 {
+            index.merge_similar(classes, ocr_tolerance);
+        }
+
+        index
+    }
+
+    /// Merges each class of words into the most frequent class within the tolerated edit distance
+    fn merge_similar(&mut self, classes: HashMap<String, &'a Word>, ocr_tolerance: &OcrTolerance) {
+        let mut classes = classes
+            .into_iter()
+            .map(|(normalized, word)| (normalized.chars().collect::<Vec<_>>(), word))
+            .collect::<Vec<_>>();
+        classes.sort_by_key(|(normalized, word)| {
+            (usize::MAX - self.occurrences(word), normalized.clone())
+        });
+
+        let mut merged: HashMap<&Word, &Word> = HashMap::default();
+        for (i, (normalized, word)) in classes.iter().enumerate() {
+            if let Some((_, target)) = classes[..i].iter().find(|(other, target)| {
+                !merged.contains_key(target) && ocr_tolerance.tolerates(normalized, other)
+            }) {
+                merged.insert(word, target);
+            }
+        }
+
+        for (word, target) in &merged {
+            if let Some(postings) = self.postings.remove(word) {
+                let target_postings = self.postings.entry(target).or_default();
+                target_postings.extend(postings);
+                target_postings.sort_unstable();
+            }
+            if let Some(positions) = &mut self.positions {
+                if let Some(word_positions) = positions.remove(word) {
+                    let target_positions = positions.entry(target).or_default();
+                    target_positions.extend(word_positions);
+                    target_positions.sort_unstable();
+                }
+            }
+        }
+        for class in self.canonical.values_mut() {
+            if let Some(target) = merged.get(class) {
+                *class = target;
+            }
+        }
+    }
+
+    /// Returns the word representing all words with the same normalized form as `word`
+    pub fn canonical(&self, word: &'a Word) -> &'a Word {
+        self.canonical.get(word).copied().unwrap_or(word)
+    }
+
+    /// Returns true if `word` was indexed, i.e. it was not excluded by the filter
+    pub fn contains(&self, word: &Word) -> bool {
+        self.postings
+            .contains_key(self.canonical.get(word).copied().unwrap_or(word))
+    }
+
+    /// Returns the word representing `word` in the index, or `None` if `word` was not indexed
+    pub fn indexed(&self, word: &Word) -> Option<&'a Word> {
+        self.postings
+            .get_key_value(self.canonical.get(word).copied().unwrap_or(word))
+            .map(|(&word, _)| word)
     }
 
     pub fn sentences(&self, word: &Word) -> impl Iterator<Item = Axis> + '_ {
-        self.0.get(word).into_iter().flatten().copied()
+        self.postings
+            .get(self.canonical.get(word).copied().unwrap_or(word))
+            .into_iter()
+            .flatten()
+            .copied()
     }
 
     pub fn occurrences(&self, word: &Word) -> usize {
-        self.0.get(word).map(|v| v.len()).unwrap_or(0)
+        self.postings
+            .get(self.canonical.get(word).copied().unwrap_or(word))
+            .map(|v| v.len())
+            .unwrap_or(0)
+    }
+
+    /// Returns the sentences containing `word` with the offset of each occurrence within the sentence.
+    /// Always empty unless positions were recorded
+    pub fn positions(&self, word: &Word) -> impl Iterator<Item = (Axis, usize)> + '_ {
+        self.positions
+            .as_ref()
+            .and_then(|positions| positions.get(self.canonical.get(word).copied().unwrap_or(word)))
+            .into_iter()
+            .flatten()
+            .copied()
+    }
+
+    /// Returns the mean offset of the occurrences of `word` within their sentences, relative to the length
+    /// of each sentence, or `0.5` if positions were not recorded
+    pub fn mean_offset(&self, word: &Word) -> f32 {
+        let (sum, count) =
+            self.positions(word)
+                .fold((0.0, 0), |(sum, count), (sentence, offset)| {
+                    let length = self.lengths[sentence.into()] as f32;
+                    (sum + (offset as f32 + 0.5) / length, count + 1)
+                });
+        if count == 0 {
+            0.5
+        } else {
+            sum / count as f32
+        }
+    }
+
+    /// Returns the number of occurrences of each distinct word indexed
+    pub fn occurrence_counts(&self) -> impl Iterator<Item = usize> + '_ {
+        self.postings.values().map(|postings| postings.len())
+    }
+
+    /// Returns the number of words indexed in each sentence
+    pub fn indexed_lengths(&self) -> &[usize] {
+        &self.indexed_lengths
+    }
+
+    /// Returns the number of distinct words indexed
+    pub fn vocabulary(&self) -> usize {
+        self.postings.len()
+    }
+
+    /// Returns the approximate memory held by the index, in bytes
+    pub fn memory(&self) -> usize {
+        let postings = self
+            .postings
+            .values()
+            .map(|postings| {
+                size_of::<&Word>()
+                    + size_of::<Vec<Axis>>()
+                    + postings.capacity() * size_of::<Axis>()
+            })
+            .sum::<usize>();
+        let positions = self.positions.iter().flatten().map(|(_, positions)| {
+            size_of::<&Word>()
+                + size_of::<Vec<(Axis, usize)>>()
+                + positions.capacity() * size_of::<(Axis, usize)>()
+        });
+
+        postings
+            + positions.sum::<usize>()
+            + self.canonical.len() * 2 * size_of::<&Word>()
+            + self.lengths.capacity() * size_of::<usize>()
+            + self.indexed_lengths.capacity() * size_of::<usize>()
+    }
+
+    /// Returns the mean position of the sentences containing `word`, relative to the length of the text
+    pub fn mean_position(&self, word: &Word) -> f32 {
+        let occurrences = self.occurrences(word);
+        if occurrences == 0 {
+            return 0.0;
+        }
+
+        let sum = self
+            .sentences(word)
+            .map(|sentence| sentence.into() as f32 + 0.5)
+            .sum::<f32>();
+        sum / occurrences as f32 / self.len as f32
     }
 }
@@ -0,0 +1,54 @@
+use std::io::{self, Write};
+use std::ops::Range;
+
+use super::Output;
+
+impl<'a, T, U> Output<'a, T, U> {
+    /// Writes the alignment to `writer` as XCES standoff alignment XML, with one link per block of aligned
+    /// sentences. The sentences are referenced by the identifiers given by `a_id` and `b_id` (e.g.
+    /// `|i| format!("s{}", i + 1)`) in the documents `from_doc` and `to_doc`, which are left untouched
+    pub fn export_xces(
+        &self,
+        mut writer: impl Write,
+        from_doc: &str,
+        to_doc: &str,
+        a_id: impl Fn(usize) -> String,
+        b_id: impl Fn(usize) -> String,
+    ) -> io::Result<()> {
+        writeln!(writer, r#"<?xml version="1.0" encoding="utf-8"?>"#)?;
+        writeln!(
+            writer,
+            r#"<!DOCTYPE cesAlign PUBLIC "-//CES//DTD XML cesAlign//EN" "">"#
+        )?;
+        writeln!(writer, r#"<cesAlign version="1.0">"#)?;
+        writeln!(
+            writer,
+            r#"<linkGrp targType="s" fromDoc="{}" toDoc="{}">"#,
+            escape(from_doc),
+            escape(to_doc)
+        )?;
+        let ids = |range: Range<usize>, id: &dyn Fn(usize) -> String| {
+            range.map(|i| escape(&id(i))).collect::<Vec<_>>().join(" ")
+        };
+        for (i, block) in self.blocks().into_iter().enumerate() {
+            writeln!(
+                writer,
+                r#"<link id="SL{}" xtargets="{};{}" certainty="{}" />"#,
+                i + 1,
+                ids(block.a_range.clone(), &a_id),
+                ids(block.b_range.clone(), &b_id),
+                block.confidence
+            )?;
+        }
+        writeln!(writer, "</linkGrp>")?;
+        writeln!(writer, "</cesAlign>")
+    }
+}
+
+/// Escapes the characters of `text` which may not appear in an XML attribute value
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}